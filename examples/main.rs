@@ -4,27 +4,25 @@ use mcl_rust::*;
 #[allow(non_snake_case)]
 fn main() {
     println!("mcl version={:04x}", get_version());
-    let b = init(CurveType::BN254);
-    if !b {
-        println!("init err");
+    if let Err(e) = init(CurveType::BN254) {
+        println!("init err {}", e);
     }
     let mut x = Fr::zero();
-    println!("x={}", x.get_str(10));
+    println!("x={}", x.get_str(10).unwrap());
     x.set_int(123456);
-    println!("x={}", x.get_str(10));
+    println!("x={}", x.get_str(10).unwrap());
     x.set_int(0xfff);
-    println!("x={}", x.get_str(16));
+    println!("x={}", x.get_str(16).unwrap());
     x.clear();
-    println!("x={}", x.get_str(10));
-    x.set_str("0x123", 0);
-    println!("x={}", x.get_str(16));
-    let buf = x.serialize();
+    println!("x={}", x.get_str(10).unwrap());
+    x.set_str("0x123", 0).unwrap();
+    println!("x={}", x.get_str(16).unwrap());
+    let buf = x.serialize().unwrap();
     println!("serialize={:x?}", buf); // put hex byte
     let mut y = Fr::zero();
-    if y.deserialize(&buf) {
-        println!("y={}", y.get_str(16));
-    } else {
-        println!("err deserialize");
+    match y.deserialize(&buf) {
+        Ok(()) => println!("y={}", y.get_str(16).unwrap()),
+        Err(e) => println!("err deserialize {}", e),
     }
     if x != y {
         println!("ng");
@@ -38,33 +36,27 @@ fn main() {
     }
     x.set_int(123);
     y.set_int(567);
-    let mut z = unsafe { Fr::uninit() };
+    let mut z = Fr::zero();
     Fr::add(&mut z, &x, &y);
 
-    let x1 = Fr::from_str("1234", 10).unwrap();
-    println!("x1={}", x1.get_str(10));
+    let x1 = Fr::from_str_radix("1234", 10).unwrap();
+    println!("x1={}", x1.get_str(10).unwrap());
 
-    println!("z={}", z.get_str(10));
-    println!("x={}", x.get_str(10));
-    println!("y={}", y.get_str(10));
+    println!("z={}", z.get_str(10).unwrap());
+    println!("x={}", x.get_str(10).unwrap());
+    println!("y={}", y.get_str(10).unwrap());
 
-    let mut P1 = unsafe { G1::uninit() };
-    let mut P2 = unsafe { G1::uninit() };
-    let mut Q1 = unsafe { G2::uninit() };
-    let mut Q2 = unsafe { G2::uninit() };
-    let mut e1 = unsafe { GT::uninit() };
-    let mut e2 = unsafe { GT::uninit() };
-    let mut e3 = unsafe { GT::uninit() };
-    P1.set_hash_of("abc".as_bytes());
-    Q1.set_hash_of("abc".as_bytes());
+    let P1 = G1::generator().unwrap();
+    let Q1 = G2::generator().unwrap();
+    let mut e1 = GT::zero();
+    let mut e2 = GT::zero();
     pairing(&mut e1, &P1, &Q1);
     x.set_by_csprng();
     y.set_by_csprng();
-    G1::mul(&mut P2, &P1, &x);
-    G2::mul(&mut Q2, &Q1, &y);
+    let P2 = P1.mul_scalar(&x);
+    let Q2 = Q1.mul_scalar(&y);
     pairing(&mut e2, &P2, &Q2);
-    GT::pow(&mut e3, &e1, &x);
-    GT::pow(&mut e1, &e3, &y);
+    let e1 = e1.pow_scalar(&x).pow_scalar(&y);
     if e1 == e2 {
         println!("ok");
     } else {
@@ -0,0 +1,197 @@
+// Conversions between mcl types and arkworks types.
+//
+// mcl selects its curve at run time, so every conversion checks that init
+// was called with the matching curve. arkworks' bn254 is mcl's SNARK curve
+// (alt_bn128), not mcl's BN254.
+//
+// Both sides use the tower Fp12 = Fp6[w]/(w^2 - v), Fp6 = Fp2[v]/(v^3 - xi),
+// so GT maps coefficient by coefficient.
+use crate::{get_curve_type, CurveType, Error, Fp, Fp2, Fr, Result, G1, G2, GT};
+use ark_ec::pairing::PairingOutput;
+use ark_ec::short_weierstrass::{Affine, Projective};
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+use core::convert::{TryFrom, TryInto};
+
+fn curve_check(curve: CurveType) -> Result<()> {
+    match get_curve_type() {
+        None => Err(Error::NotInitialized),
+        Some(c) if c == curve => Ok(()),
+        Some(_) => Err(Error::CurveMismatch),
+    }
+}
+
+macro_rules! owned_impl {
+    ($from:ty, $to:ty) => {
+        impl TryFrom<$from> for $to {
+            type Error = Error;
+            fn try_from(x: $from) -> Result<$to> {
+                <$to>::try_from(&x)
+            }
+        }
+    };
+}
+
+// canonical little endian bytes in between
+macro_rules! field_impl {
+    ($t:ty, $ark:ty, $curve:expr) => {
+        impl TryFrom<&$t> for $ark {
+            type Error = Error;
+            fn try_from(x: &$t) -> Result<$ark> {
+                curve_check($curve)?;
+                let mut buf = [0u8; 48];
+                x.write_little_endian(&mut buf)?;
+                Ok(<$ark>::from_le_bytes_mod_order(&buf))
+            }
+        }
+        impl TryFrom<&$ark> for $t {
+            type Error = Error;
+            fn try_from(x: &$ark) -> Result<$t> {
+                curve_check($curve)?;
+                let mut v = <$t>::zero();
+                v.set_little_endian(&x.into_bigint().to_bytes_le())?;
+                Ok(v)
+            }
+        }
+        owned_impl![$t, $ark];
+        owned_impl![$ark, $t];
+    };
+}
+
+macro_rules! fp2_impl {
+    ($fq:ty, $fq2:ty) => {
+        impl TryFrom<&Fp2> for $fq2 {
+            type Error = Error;
+            fn try_from(x: &Fp2) -> Result<$fq2> {
+                Ok(<$fq2>::new(
+                    <$fq>::try_from(&x.d[0])?,
+                    <$fq>::try_from(&x.d[1])?,
+                ))
+            }
+        }
+        impl TryFrom<&$fq2> for Fp2 {
+            type Error = Error;
+            fn try_from(x: &$fq2) -> Result<Fp2> {
+                Ok(Fp2 {
+                    d: [Fp::try_from(&x.c0)?, Fp::try_from(&x.c1)?],
+                })
+            }
+        }
+        owned_impl![Fp2, $fq2];
+        owned_impl![$fq2, Fp2];
+    };
+}
+
+macro_rules! ec_impl {
+    ($t:ident, $base:ty, $affine:ty, $proj:ty, $curve:expr) => {
+        impl TryFrom<&$t> for $affine {
+            type Error = Error;
+            fn try_from(p: &$t) -> Result<$affine> {
+                curve_check($curve)?;
+                if p.is_zero() {
+                    return Ok(<$affine>::identity());
+                }
+                let p = p.normalized();
+                Ok(<$affine>::new_unchecked(
+                    (&p.x).try_into()?,
+                    (&p.y).try_into()?,
+                ))
+            }
+        }
+        impl TryFrom<&$affine> for $t {
+            type Error = Error;
+            fn try_from(p: &$affine) -> Result<$t> {
+                curve_check($curve)?;
+                if p.infinity {
+                    return Ok(<$t>::zero());
+                }
+                Ok($t {
+                    x: (&p.x).try_into()?,
+                    y: (&p.y).try_into()?,
+                    z: <$base>::one(),
+                })
+            }
+        }
+        impl TryFrom<&$t> for $proj {
+            type Error = Error;
+            fn try_from(p: &$t) -> Result<$proj> {
+                <$affine>::try_from(p).map(Into::into)
+            }
+        }
+        impl TryFrom<&$proj> for $t {
+            type Error = Error;
+            fn try_from(p: &$proj) -> Result<$t> {
+                <$t>::try_from(&p.into_affine())
+            }
+        }
+        owned_impl![$t, $affine];
+        owned_impl![$affine, $t];
+        owned_impl![$t, $proj];
+        owned_impl![$proj, $t];
+    };
+}
+
+macro_rules! gt_impl {
+    ($engine:ty, $fq2:ty, $fq6:ty, $fq12:ty) => {
+        impl TryFrom<&GT> for PairingOutput<$engine> {
+            type Error = Error;
+            fn try_from(x: &GT) -> Result<PairingOutput<$engine>> {
+                let mut c = [<$fq2>::default(); 6];
+                for (i, c) in c.iter_mut().enumerate() {
+                    *c = <$fq2>::try_from(&Fp2 {
+                        d: [x.d[i * 2], x.d[i * 2 + 1]],
+                    })?;
+                }
+                Ok(PairingOutput(<$fq12>::new(
+                    <$fq6>::new(c[0], c[1], c[2]),
+                    <$fq6>::new(c[3], c[4], c[5]),
+                )))
+            }
+        }
+        impl TryFrom<&PairingOutput<$engine>> for GT {
+            type Error = Error;
+            fn try_from(x: &PairingOutput<$engine>) -> Result<GT> {
+                let mut v = GT::zero();
+                let c = [
+                    &x.0.c0.c0, &x.0.c0.c1, &x.0.c0.c2, &x.0.c1.c0, &x.0.c1.c1, &x.0.c1.c2,
+                ];
+                for (i, c) in c.iter().enumerate() {
+                    let c = Fp2::try_from(*c)?;
+                    v.d[i * 2] = c.d[0];
+                    v.d[i * 2 + 1] = c.d[1];
+                }
+                Ok(v)
+            }
+        }
+        owned_impl![GT, PairingOutput<$engine>];
+        owned_impl![PairingOutput<$engine>, GT];
+    };
+}
+
+macro_rules! curve_impl {
+    ($ark:ident, $engine:ident, $curve:expr) => {
+        field_impl![Fr, $ark::Fr, $curve];
+        field_impl![Fp, $ark::Fq, $curve];
+        fp2_impl![$ark::Fq, $ark::Fq2];
+        // G1Affine and the like are aliases that coherence cannot see through
+        ec_impl![
+            G1,
+            Fp,
+            Affine<$ark::g1::Config>,
+            Projective<$ark::g1::Config>,
+            $curve
+        ];
+        ec_impl![
+            G2,
+            Fp2,
+            Affine<$ark::g2::Config>,
+            Projective<$ark::g2::Config>,
+            $curve
+        ];
+        gt_impl![$ark::$engine, $ark::Fq2, $ark::Fq6, $ark::Fq12];
+    };
+}
+
+curve_impl![ark_bn254, Bn254, CurveType::SNARK];
+curve_impl![ark_bls12_381, Bls12_381, CurveType::BLS12_381];
+curve_impl![ark_bls12_377, Bls12_377, CurveType::BLS12_377];
@@ -1,15 +1,51 @@
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 use alloc::string::String;
+use alloc::vec;
 use alloc::vec::Vec;
-use core::mem::MaybeUninit;
+use core::cmp::Ordering as CmpOrdering;
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{Product, Sum};
+use core::ops::Neg;
 use core::ops::{Add, AddAssign};
+use core::ops::{BitOr, BitOrAssign};
 use core::ops::{Div, DivAssign};
 use core::ops::{Mul, MulAssign};
 use core::ops::{Sub, SubAssign};
 use core::primitive::str;
+use core::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+#[cfg(feature = "arkworks")]
+mod arkworks_impl;
+#[cfg(feature = "num-bigint")]
+mod num_bigint_impl;
+#[cfg(feature = "rand_core")]
+mod rand_impl;
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "sha2")]
+mod sha2_impl;
+#[cfg(feature = "subtle")]
+mod subtle_impl;
+#[cfg(feature = "zkcrypto")]
+pub mod zkcrypto;
+
+#[cfg(feature = "rand_core")]
+pub use rand_impl::{reset_rand_func, set_rand_func};
+#[cfg(feature = "zeroize")]
+pub use secret::SecretFr;
+#[cfg(feature = "sha2")]
+pub use sha2_impl::expand_message_xmd;
 
 #[link(name = "mcl", kind = "static")]
 #[cfg_attr(target_arch = "x86_64", link(name = "stdc++"))]
@@ -25,6 +61,30 @@ extern "C" {
     fn mclBn_pairing(z: *mut GT, x: *const G1, y: *const G2);
     fn mclBn_millerLoop(z: *mut GT, x: *const G1, y: *const G2);
     fn mclBn_finalExp(y: *mut GT, x: *const GT);
+    fn mclBn_millerLoopVec(z: *mut GT, x: *const G1, y: *const G2, n: usize);
+    fn mclBn_millerLoopVecMT(z: *mut GT, x: *const G1, y: *const G2, n: usize, cpuN: usize);
+    fn mclBn_getUint64NumToPrecompute() -> i32;
+    fn mclBn_precomputeG2(Qbuf: *mut u64, Q: *const G2);
+    fn mclBn_precomputedMillerLoop(f: *mut GT, P: *const G1, Qbuf: *const u64);
+    fn mclBn_precomputedMillerLoop2(
+        f: *mut GT,
+        P1: *const G1,
+        Q1buf: *const u64,
+        P2: *const G1,
+        Q2buf: *const u64,
+    );
+    fn mclBn_setETHserialization(enable: i32);
+    fn mclBn_setMapToMode(mode: i32) -> i32;
+    fn mclBn_getETHserialization() -> i32;
+    fn mclBn_verifyOrderG1(doVerify: i32);
+    fn mclBn_verifyOrderG2(doVerify: i32);
+    #[cfg(feature = "rand_core")]
+    fn mclBn_setRandFunc(
+        self_: *mut core::ffi::c_void,
+        readFunc: Option<
+            unsafe extern "C" fn(*mut core::ffi::c_void, *mut core::ffi::c_void, u32) -> u32,
+        >,
+    );
 
     // Fr
     fn mclBnFr_isEqual(x: *const Fr, y: *const Fr) -> i32;
@@ -43,6 +103,8 @@ extern "C" {
     fn mclBnFr_setInt32(x: *mut Fr, v: i32);
     fn mclBnFr_setLittleEndian(x: *mut Fr, buf: *const u8, bufSize: usize) -> i32;
     fn mclBnFr_setLittleEndianMod(x: *mut Fr, buf: *const u8, bufSize: usize) -> i32;
+    fn mclBnFr_setBigEndianMod(x: *mut Fr, buf: *const u8, bufSize: usize) -> i32;
+    fn mclBnFr_getLittleEndian(buf: *mut u8, maxBufSize: usize, x: *const Fr) -> usize;
     fn mclBnFr_setHashOf(x: *mut Fr, buf: *const u8, bufSize: usize) -> i32;
     fn mclBnFr_setByCSPRNG(x: *mut Fr);
 
@@ -73,6 +135,8 @@ extern "C" {
     fn mclBnFp_setInt32(x: *mut Fp, v: i32);
     fn mclBnFp_setLittleEndian(x: *mut Fp, buf: *const u8, bufSize: usize) -> i32;
     fn mclBnFp_setLittleEndianMod(x: *mut Fp, buf: *const u8, bufSize: usize) -> i32;
+    fn mclBnFp_setBigEndianMod(x: *mut Fp, buf: *const u8, bufSize: usize) -> i32;
+    fn mclBnFp_getLittleEndian(buf: *mut u8, maxBufSize: usize, x: *const Fp) -> usize;
     fn mclBnFp_setHashOf(x: *mut Fp, buf: *const u8, bufSize: usize) -> i32;
     fn mclBnFp_setByCSPRNG(x: *mut Fp);
 
@@ -85,6 +149,7 @@ extern "C" {
     fn mclBnFp_inv(y: *mut Fp, x: *const Fp);
     fn mclBnFp_sqr(y: *mut Fp, x: *const Fp);
     fn mclBnFp_squareRoot(y: *mut Fp, x: *const Fp) -> i32;
+    fn mclBnFp_mapToG1(y: *mut G1, x: *const Fp) -> i32;
 
     // Fp2
     fn mclBnFp2_isEqual(x: *const Fp2, y: *const Fp2) -> i32;
@@ -102,11 +167,13 @@ extern "C" {
     fn mclBnFp2_inv(y: *mut Fp2, x: *const Fp2);
     fn mclBnFp2_sqr(y: *mut Fp2, x: *const Fp2);
     fn mclBnFp2_squareRoot(y: *mut Fp2, x: *const Fp2) -> i32;
+    fn mclBnFp2_mapToG2(y: *mut G2, x: *const Fp2) -> i32;
 
     // G1
     fn mclBnG1_isEqual(x: *const G1, y: *const G1) -> i32;
     fn mclBnG1_isValid(x: *const G1) -> i32;
     fn mclBnG1_isZero(x: *const G1) -> i32;
+    fn mclBnG1_isValidOrder(x: *const G1) -> i32;
 
     fn mclBnG1_setStr(x: *mut G1, buf: *const u8, bufSize: usize, ioMode: i32) -> i32;
     fn mclBnG1_getStr(buf: *mut u8, maxBufSize: usize, x: *const G1, ioMode: i32) -> usize;
@@ -119,14 +186,28 @@ extern "C" {
 
     fn mclBnG1_dbl(y: *mut G1, x: *const G1);
     fn mclBnG1_mul(z: *mut G1, x: *const G1, y: *const Fr);
+    fn mclBnG1_mulCT(z: *mut G1, x: *const G1, y: *const Fr);
     fn mclBnG1_normalize(y: *mut G1, x: *const G1);
     fn mclBnG1_hashAndMapTo(x: *mut G1, buf: *const u8, bufSize: usize) -> i32;
+    fn mclBnG1_hashAndMapToWithDst(
+        x: *mut G1,
+        buf: *const u8,
+        bufSize: usize,
+        dst: *const u8,
+        dstSize: usize,
+    ) -> i32;
+    fn mclBnG1_setDst(dst: *const u8, dstSize: usize) -> i32;
     fn mclBnG1_mulVec(z: *mut G1, x: *const G1, y: *const Fr, n: usize);
+    fn mclBnG1_mulVecMT(z: *mut G1, x: *const G1, y: *const Fr, n: usize, cpuN: usize);
+    // both return an int that is always zero, so it is dropped here
+    fn mclBnG1_getBasePoint(x: *mut G1);
+    fn mclBn_setGeneratorOfG1(x: *const G1);
 
     // G2
     fn mclBnG2_isEqual(x: *const G2, y: *const G2) -> i32;
     fn mclBnG2_isValid(x: *const G2) -> i32;
     fn mclBnG2_isZero(x: *const G2) -> i32;
+    fn mclBnG2_isValidOrder(x: *const G2) -> i32;
 
     fn mclBnG2_setStr(x: *mut G2, buf: *const u8, bufSize: usize, ioMode: i32) -> i32;
     fn mclBnG2_getStr(buf: *mut u8, maxBufSize: usize, x: *const G2, ioMode: i32) -> usize;
@@ -139,9 +220,22 @@ extern "C" {
 
     fn mclBnG2_dbl(y: *mut G2, x: *const G2);
     fn mclBnG2_mul(z: *mut G2, x: *const G2, y: *const Fr);
+    fn mclBnG2_mulCT(z: *mut G2, x: *const G2, y: *const Fr);
     fn mclBnG2_normalize(y: *mut G2, x: *const G2);
     fn mclBnG2_hashAndMapTo(x: *mut G2, buf: *const u8, bufSize: usize) -> i32;
+    fn mclBnG2_hashAndMapToWithDst(
+        x: *mut G2,
+        buf: *const u8,
+        bufSize: usize,
+        dst: *const u8,
+        dstSize: usize,
+    ) -> i32;
+    fn mclBnG2_setDst(dst: *const u8, dstSize: usize) -> i32;
     fn mclBnG2_mulVec(z: *mut G2, x: *const G2, y: *const Fr, n: usize);
+    fn mclBnG2_mulVecMT(z: *mut G2, x: *const G2, y: *const Fr, n: usize, cpuN: usize);
+    // both return an int that is always zero, so it is dropped here
+    fn mclBnG2_getBasePoint(x: *mut G2);
+    fn mclBn_setGeneratorOfG2(x: *const G2);
 
     // GT
     fn mclBnGT_isEqual(x: *const GT, y: *const GT) -> i32;
@@ -165,9 +259,36 @@ extern "C" {
     fn mclBnGT_sqr(y: *mut GT, x: *const GT);
 
     fn mclBnGT_pow(z: *mut GT, x: *const GT, y: *const Fr);
+    fn mclBnGT_powGeneric(z: *mut GT, x: *const GT, y: *const Fr);
+
+    // polynomials
+    fn mclBn_FrEvaluatePolynomial(out: *mut Fr, cVec: *const Fr, cSize: usize, x: *const Fr)
+        -> i32;
+    fn mclBn_G1EvaluatePolynomial(out: *mut G1, cVec: *const G1, cSize: usize, x: *const Fr)
+        -> i32;
+    fn mclBn_G2EvaluatePolynomial(out: *mut G2, cVec: *const G2, cSize: usize, x: *const Fr)
+        -> i32;
+    fn mclBn_FrLagrangeInterpolation(
+        out: *mut Fr,
+        xVec: *const Fr,
+        yVec: *const Fr,
+        k: usize,
+    ) -> i32;
+    fn mclBn_G1LagrangeInterpolation(
+        out: *mut G1,
+        xVec: *const Fr,
+        yVec: *const G1,
+        k: usize,
+    ) -> i32;
+    fn mclBn_G2LagrangeInterpolation(
+        out: *mut G2,
+        xVec: *const Fr,
+        yVec: *const G2,
+        k: usize,
+    ) -> i32;
 }
 
-#[derive(PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum CurveType {
     BN254 = 0,
     BN381 = 1,
@@ -178,6 +299,201 @@ pub enum CurveType {
     BN_P256 = 9,
 }
 
+impl CurveType {
+    // byte sizes of the serialized forms, as reported by get_fr_serialized_size
+    // and friends once init has been called with this curve; None for BN381,
+    // whose Fr does not fit this build, so a size never exceeds the type's
+    // MAX_SERIALIZED_SIZE
+    pub const fn fr_serialized_size(self) -> Option<usize> {
+        match self {
+            CurveType::BN381 => None,
+            _ => Some(32),
+        }
+    }
+    pub const fn fp_serialized_size(self) -> Option<usize> {
+        match self {
+            CurveType::BN381 => None,
+            CurveType::BN254 | CurveType::SNARK | CurveType::BN_P256 => Some(32),
+            _ => Some(48),
+        }
+    }
+    pub const fn g1_serialized_size(self) -> Option<usize> {
+        self.fp_serialized_size()
+    }
+    pub const fn g2_serialized_size(self) -> Option<usize> {
+        match self.fp_serialized_size() {
+            Some(n) => Some(n * 2),
+            None => None,
+        }
+    }
+    pub const fn gt_serialized_size(self) -> Option<usize> {
+        match self.fp_serialized_size() {
+            Some(n) => Some(n * 12),
+            None => None,
+        }
+    }
+    fn from_i32(v: i32) -> Option<CurveType> {
+        match v {
+            0 => Some(CurveType::BN254),
+            1 => Some(CurveType::BN381),
+            4 => Some(CurveType::SNARK),
+            5 => Some(CurveType::BLS12_381),
+            8 => Some(CurveType::BLS12_377),
+            9 => Some(CurveType::BN_P256),
+            _ => None,
+        }
+    }
+}
+
+// how set_hash_of and hash_to_curve map to G1 and G2
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum MapToMode {
+    Original = 0,
+    TryAndInc = 1,
+    // RFC 9380 SSWU, BLS12_381 only
+    HashToCurve = 5,
+}
+
+/// The `ioMode` flags of mclBn*_getStr/setStr.
+///
+/// An `i32` converts into it, so `get_str(16)` still works.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct IoMode(i32);
+
+impl IoMode {
+    pub const AUTO: IoMode = IoMode(0);
+    pub const BIN: IoMode = IoMode(2);
+    pub const DEC: IoMode = IoMode(10);
+    pub const HEX: IoMode = IoMode(16);
+    // fixed size array of limbs
+    pub const ARRAY: IoMode = IoMode(32);
+    // same without Montgomery conversion
+    pub const ARRAY_RAW: IoMode = IoMode(64);
+    // 0b or 0x
+    pub const PREFIX: IoMode = IoMode(128);
+    pub const EC_AFFINE: IoMode = IoMode(0);
+    pub const EC_COMP: IoMode = IoMode(256);
+    pub const SERIALIZE: IoMode = IoMode(512);
+    pub const EC_PROJ: IoMode = IoMode(1024);
+    pub const SERIALIZE_HEX_STR: IoMode = IoMode(2048);
+    // x and y without compression
+    pub const EC_AFFINE_SERIALIZE: IoMode = IoMode(4096);
+    pub const BIG_ENDIAN: IoMode = IoMode(8192);
+
+    pub fn bits(self) -> i32 {
+        self.0
+    }
+    pub fn contains(self, other: IoMode) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl From<i32> for IoMode {
+    fn from(v: i32) -> IoMode {
+        IoMode(v)
+    }
+}
+
+impl BitOr for IoMode {
+    type Output = IoMode;
+    fn bitor(self, other: IoMode) -> IoMode {
+        IoMode(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for IoMode {
+    fn bitor_assign(&mut self, other: IoMode) {
+        self.0 |= other.0
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum Error {
+    InitFailed,
+    NotInitialized,
+    InvalidEncoding,
+    NotInSubgroup,
+    HashFailed,
+    NoSquareRoot,
+    BufferTooSmall,
+    CurveMismatch,
+    Unsupported,
+    InvalidLength,
+    DuplicateId,
+    ZeroId,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Error::InitFailed => "mclBn_init failed",
+            Error::NotInitialized => "init() has not been called",
+            Error::InvalidEncoding => "invalid encoding",
+            Error::NotInSubgroup => "not in the prime order subgroup",
+            Error::HashFailed => "hash failed",
+            Error::NoSquareRoot => "no square root",
+            Error::BufferTooSmall => "buffer too small",
+            Error::CurveMismatch => "initialized with another curve",
+            Error::Unsupported => "not supported in the current mode or curve",
+            Error::InvalidLength => "empty or mismatched slices",
+            Error::DuplicateId => "duplicate id",
+            Error::ZeroId => "zero id",
+        };
+        f.write_str(s)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+fn check(ret: i32, err: Error) -> Result<()> {
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(err)
+    }
+}
+
+// curve passed to the last successful init, -1 before that
+static CURVE_TYPE: AtomicI32 = AtomicI32::new(-1);
+
+fn init_check() -> Result<()> {
+    if is_initialized() {
+        Ok(())
+    } else {
+        Err(Error::NotInitialized)
+    }
+}
+
+// mode passed to the last successful set_map_to_mode since init, -1 before that
+static MAP_TO_MODE: AtomicI32 = AtomicI32::new(-1);
+
+fn hash_to_curve_check() -> Result<()> {
+    init_check()?;
+    if MAP_TO_MODE.load(Ordering::Acquire) == MapToMode::HashToCurve as i32 {
+        Ok(())
+    } else {
+        Err(Error::Unsupported)
+    }
+}
+
+// whether deserialize checks that G1, G2 and GT elements are in the prime
+// order subgroup; libmcl's own check is turned off at init so that the crate
+// decides, see deserialize_checked and deserialize_unchecked
+static VERIFY_ORDER_G1: AtomicBool = AtomicBool::new(true);
+static VERIFY_ORDER_G2: AtomicBool = AtomicBool::new(true);
+static VERIFY_ORDER_GT: AtomicBool = AtomicBool::new(true);
+
+// for infallible functions; calling into libmcl before init is undefined behaviour
+fn assert_init() {
+    if !is_initialized() {
+        panic!("mcl_rust: init() has not been called");
+    }
+}
+
 const MCLBN_FP_UNIT_SIZE: usize = 6;
 const MCLBN_FR_UNIT_SIZE: usize = 4;
 const MCLBN_COMPILED_TIME_VAR: i32 = MCLBN_FR_UNIT_SIZE as i32 * 10 + MCLBN_FP_UNIT_SIZE as i32;
@@ -186,31 +502,39 @@ macro_rules! common_impl {
     ($t:ty, $is_equal_fn:ident, $is_zero_fn:ident) => {
         impl PartialEq for $t {
             fn eq(&self, rhs: &Self) -> bool {
+                assert_init();
                 unsafe { $is_equal_fn(self, rhs) == 1 }
             }
         }
+        impl Eq for $t {}
         impl $t {
             pub fn zero() -> $t {
                 Default::default()
             }
-            pub unsafe fn uninit() -> $t {
-                let u = MaybeUninit::<$t>::uninit();
-                let v = unsafe { u.assume_init() };
-                v
-            }
             pub fn clear(&mut self) {
                 *self = <$t>::zero()
             }
             pub fn is_zero(&self) -> bool {
+                assert_init();
                 unsafe { $is_zero_fn(self) == 1 }
             }
         }
+        #[cfg(feature = "zeroize")]
+        impl zeroize::Zeroize for $t {
+            // every type is a plain array of u64 whose all-zero bytes are zero()
+            fn zeroize(&mut self) {
+                let p = self as *mut $t as *mut u8;
+                let buf = unsafe { core::slice::from_raw_parts_mut(p, core::mem::size_of::<$t>()) };
+                zeroize::Zeroize::zeroize(buf);
+            }
+        }
     };
 }
 macro_rules! is_valid_impl {
     ($t:ty, $is_valid_fn:ident) => {
         impl $t {
             pub fn is_valid(&self) -> bool {
+                assert_init();
                 unsafe { $is_valid_fn(self) == 1 }
             }
         }
@@ -218,54 +542,156 @@ macro_rules! is_valid_impl {
 }
 
 macro_rules! serialize_impl {
-    ($t:ty, $size:expr, $serialize_fn:ident, $deserialize_fn:ident) => {
+    ($t:ty, $max_size:expr, $serialize_fn:ident, $deserialize_fn:ident) => {
+        serialize_impl![$t, $max_size, $serialize_fn, $deserialize_fn, |_| Ok(())];
+    };
+    ($t:ty, $max_size:expr, $serialize_fn:ident, $deserialize_fn:ident, $check:expr) => {
         impl $t {
-            pub fn deserialize(&mut self, buf: &[u8]) -> bool {
-                unsafe { $deserialize_fn(self, buf.as_ptr(), buf.len()) > 0 }
+            // over the curves this build supports; see CurveType for a given curve
+            pub const MAX_SERIALIZED_SIZE: usize = $max_size;
+            // rejects trailing bytes and leaves self unchanged on failure
+            pub fn deserialize(&mut self, buf: &[u8]) -> Result<()> {
+                let mut v = <$t>::zero();
+                v.deserialize_raw(buf)?;
+                let check: fn(&$t) -> Result<()> = $check;
+                check(&v)?;
+                *self = v;
+                Ok(())
             }
-            pub fn serialize(&self) -> Vec<u8> {
-                let size = unsafe { $size } as usize;
-                let mut buf: Vec<u8> = Vec::with_capacity(size);
-                let n: usize;
-                unsafe {
-                    n = $serialize_fn(buf.as_mut_ptr(), size, self);
+            // no subgroup check; a prefix that decodes is not enough, the
+            // whole buffer must be consumed
+            pub(crate) fn deserialize_raw(&mut self, buf: &[u8]) -> Result<()> {
+                init_check()?;
+                let n = unsafe { $deserialize_fn(self, buf.as_ptr(), buf.len()) };
+                if n == 0 || n != buf.len() {
+                    return Err(Error::InvalidEncoding);
                 }
+                Ok(())
+            }
+            // returns the number of bytes written
+            pub fn serialize_into(&self, out: &mut [u8]) -> Result<usize> {
+                init_check()?;
+                let n = unsafe { $serialize_fn(out.as_mut_ptr(), out.len(), self) };
                 if n == 0 {
-                    panic!("serialize");
+                    return Err(Error::BufferTooSmall);
                 }
-                unsafe {
-                    buf.set_len(n);
+                Ok(n)
+            }
+            pub fn serialize(&self) -> Result<Vec<u8>> {
+                let mut buf = [0u8; $max_size];
+                let n = self.serialize_into(&mut buf)?;
+                Ok(buf[..n].to_vec())
+            }
+        }
+    };
+}
+
+// hashes the internal limbs, which are reduced and so canonical for a given
+// curve whatever the serialization mode; points are normalized first so that
+// equal points in different jacobian coordinates hash alike
+macro_rules! hash_impl {
+    ($t:ty) => {
+        impl Hash for $t {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.d.hash(state);
+            }
+        }
+    };
+    ($t:ty, ec) => {
+        impl Hash for $t {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                // nothing but zero() is expected before init
+                if !is_initialized() || self.is_zero() {
+                    return 0u8.hash(state);
                 }
-                buf
+                let v = self.normalized();
+                1u8.hash(state);
+                v.x.hash(state);
+                v.y.hash(state);
             }
         }
     };
 }
 
+// $maxBufSize fits IoMode::BIN, one character per bit of each coordinate
 macro_rules! str_impl {
     ($t:ty, $maxBufSize:expr, $get_str_fn:ident, $set_str_fn:ident) => {
+        str_impl![$t, $maxBufSize, $get_str_fn, $set_str_fn, |_| Ok(())];
+    };
+    ($t:ty, $maxBufSize:expr, $get_str_fn:ident, $set_str_fn:ident, $check:expr) => {
         impl $t {
-            pub fn from_str(s: &str, base: i32) -> Option<$t> {
-                let mut v = unsafe { <$t>::uninit() };
-                if v.set_str(s, base) {
-                    return Some(v);
-                }
-                None
+            pub fn from_str_radix(s: &str, base: impl Into<IoMode>) -> Result<$t> {
+                let mut v = <$t>::zero();
+                v.set_str(s, base)?;
+                Ok(v)
             }
-            pub fn set_str(&mut self, s: &str, base: i32) -> bool {
-                unsafe { $set_str_fn(self, s.as_ptr(), s.len(), base) == 0 }
+            // runs the same check as deserialize and leaves self unchanged on
+            // failure
+            pub fn set_str(&mut self, s: &str, base: impl Into<IoMode>) -> Result<()> {
+                init_check()?;
+                let mut v = <$t>::zero();
+                check(
+                    unsafe { $set_str_fn(&mut v, s.as_ptr(), s.len(), base.into().bits()) },
+                    Error::InvalidEncoding,
+                )?;
+                let check: fn(&$t) -> Result<()> = $check;
+                check(&v)?;
+                *self = v;
+                Ok(())
             }
-            pub fn get_str(&self, io_mode: i32) -> String {
-                let u = MaybeUninit::<[u8; $maxBufSize]>::uninit();
-                let mut buf = unsafe { u.assume_init() };
-                let n: usize;
-                unsafe {
-                    n = $get_str_fn(buf.as_mut_ptr(), buf.len(), self, io_mode);
-                }
+            // returns the number of bytes written
+            pub fn get_str_into(
+                &self,
+                out: &mut [u8],
+                io_mode: impl Into<IoMode>,
+            ) -> Result<usize> {
+                init_check()?;
+                let n = unsafe {
+                    $get_str_fn(out.as_mut_ptr(), out.len(), self, io_mode.into().bits())
+                };
                 if n == 0 {
-                    panic!("mclBnFr_getStr");
+                    return Err(Error::BufferTooSmall);
+                }
+                Ok(n)
+            }
+            // binary modes such as IoMode::SERIALIZE fail with InvalidEncoding
+            // unless the output happens to be UTF-8
+            pub fn get_str(&self, io_mode: impl Into<IoMode>) -> Result<String> {
+                let mut buf = [0u8; $maxBufSize];
+                let n = self.get_str_into(&mut buf, io_mode)?;
+                core::str::from_utf8(&buf[0..n])
+                    .map(String::from)
+                    .map_err(|_| Error::InvalidEncoding)
+            }
+        }
+        impl core::str::FromStr for $t {
+            type Err = Error;
+            // accepts decimal, or hexadecimal with a 0x prefix
+            fn from_str(s: &str) -> Result<$t> {
+                <$t>::from_str_radix(s, 0)
+            }
+        }
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.get_str(10).map_err(|_| fmt::Error)?)
+            }
+        }
+        impl fmt::LowerHex for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.get_str(16).map_err(|_| fmt::Error)?)
+            }
+        }
+    };
+}
+
+macro_rules! debug_impl {
+    ($t:ty) => {
+        impl fmt::Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.get_str(16) {
+                    Ok(s) => write!(f, "{}({})", stringify!($t), s),
+                    Err(_) => write!(f, "{}(..)", stringify!($t)),
                 }
-                unsafe { core::str::from_utf8_unchecked(&buf[0..n]).into() }
             }
         }
     };
@@ -274,17 +700,22 @@ macro_rules! str_impl {
 macro_rules! int_impl {
     ($t:ty, $set_int_fn:ident, $is_one_fn:ident) => {
         impl $t {
+            pub fn one() -> $t {
+                <$t>::from_int(1)
+            }
             pub fn from_int(x: i32) -> $t {
-                let mut v = unsafe { <$t>::uninit() };
+                let mut v = <$t>::zero();
                 v.set_int(x);
                 v
             }
             pub fn set_int(&mut self, x: i32) {
+                assert_init();
                 unsafe {
                     $set_int_fn(self, x);
                 }
             }
             pub fn is_one(&self) -> bool {
+                assert_init();
                 unsafe { $is_one_fn(self) == 1 }
             }
         }
@@ -292,31 +723,184 @@ macro_rules! int_impl {
 }
 
 macro_rules! base_field_impl {
-    ($t:ty,  $set_little_endian_fn:ident, $set_little_endian_mod_fn:ident, $set_hash_of_fn:ident, $set_by_csprng_fn:ident, $is_odd_fn:ident, $is_negative_fn:ident, $cmp_fn:ident, $square_root_fn:ident) => {
+    ($t:ty,  $set_little_endian_fn:ident, $set_little_endian_mod_fn:ident, $get_little_endian_fn:ident, $set_hash_of_fn:ident, $set_by_csprng_fn:ident, $is_odd_fn:ident, $is_negative_fn:ident, $cmp_fn:ident, $square_root_fn:ident) => {
         impl $t {
-            pub fn set_little_endian(&mut self, buf: &[u8]) -> bool {
-                unsafe { $set_little_endian_fn(self, buf.as_ptr(), buf.len()) == 0 }
+            pub fn set_little_endian(&mut self, buf: &[u8]) -> Result<()> {
+                init_check()?;
+                check(
+                    unsafe { $set_little_endian_fn(self, buf.as_ptr(), buf.len()) },
+                    Error::InvalidEncoding,
+                )
             }
-            pub fn set_little_endian_mod(&mut self, buf: &[u8]) -> bool {
-                unsafe { $set_little_endian_mod_fn(self, buf.as_ptr(), buf.len()) == 0 }
+            pub fn set_little_endian_mod(&mut self, buf: &[u8]) -> Result<()> {
+                init_check()?;
+                check(
+                    unsafe { $set_little_endian_mod_fn(self, buf.as_ptr(), buf.len()) },
+                    Error::InvalidEncoding,
+                )
             }
-            pub fn set_hash_of(&mut self, buf: &[u8]) -> bool {
-                unsafe { $set_hash_of_fn(self, buf.as_ptr(), buf.len()) == 0 }
+            // returns the number of bytes written
+            pub(crate) fn write_little_endian(&self, buf: &mut [u8]) -> Result<usize> {
+                init_check()?;
+                let n = unsafe { $get_little_endian_fn(buf.as_mut_ptr(), buf.len(), self) };
+                if n == 0 {
+                    return Err(Error::BufferTooSmall);
+                }
+                Ok(n)
+            }
+            pub fn set_hash_of(&mut self, buf: &[u8]) -> Result<()> {
+                init_check()?;
+                check(
+                    unsafe { $set_hash_of_fn(self, buf.as_ptr(), buf.len()) },
+                    Error::HashFailed,
+                )
             }
             pub fn set_by_csprng(&mut self) {
+                assert_init();
                 unsafe { $set_by_csprng_fn(self) }
             }
             pub fn is_odd(&self) -> bool {
+                assert_init();
                 unsafe { $is_odd_fn(self) == 1 }
             }
             pub fn is_negative(&self) -> bool {
+                assert_init();
                 unsafe { $is_negative_fn(self) == 1 }
             }
-            pub fn cmp(&self, rhs: &$t) -> i32 {
-                unsafe { $cmp_fn(self, rhs) }
+            pub fn square_root(y: &mut $t, x: &$t) -> Result<()> {
+                init_check()?;
+                check(unsafe { $square_root_fn(y, x) }, Error::NoSquareRoot)
+            }
+            pub fn sqrt(&self) -> Result<$t> {
+                let mut v = <$t>::zero();
+                <$t>::square_root(&mut v, self)?;
+                Ok(v)
+            }
+        }
+        impl Ord for $t {
+            fn cmp(&self, rhs: &$t) -> CmpOrdering {
+                assert_init();
+                unsafe { $cmp_fn(self, rhs) }.cmp(&0)
             }
-            pub fn square_root(y: &mut $t, x: &$t) -> bool {
-                unsafe { $square_root_fn(y, x) == 0 }
+        }
+        impl PartialOrd for $t {
+            fn partial_cmp(&self, rhs: &$t) -> Option<CmpOrdering> {
+                Some(self.cmp(rhs))
+            }
+        }
+    };
+}
+
+// canonical integer representations, independent of the serialization mode;
+// $n bytes and $limbs u64 hold the value on every curve
+macro_rules! bytes_impl {
+    ($t:ty, $n:expr, $limbs:expr, $size_fn:ident, $set_big_endian_mod_fn:ident) => {
+        impl $t {
+            pub fn set_big_endian_mod(&mut self, buf: &[u8]) -> Result<()> {
+                init_check()?;
+                check(
+                    unsafe { $set_big_endian_mod_fn(self, buf.as_ptr(), buf.len()) },
+                    Error::InvalidEncoding,
+                )
+            }
+            fn le_array(&self) -> Result<[u8; $n]> {
+                let mut buf = [0u8; $n];
+                self.write_little_endian(&mut buf)?;
+                Ok(buf)
+            }
+            // byte size of the modulus, zero padded
+            pub fn to_bytes_le(&self) -> Result<Vec<u8>> {
+                let buf = self.le_array()?;
+                Ok(buf[..$size_fn() as usize].to_vec())
+            }
+            pub fn to_bytes_be(&self) -> Result<Vec<u8>> {
+                let mut v = self.to_bytes_le()?;
+                v.reverse();
+                Ok(v)
+            }
+            // rejects values not less than the modulus, unlike set_little_endian_mod
+            pub fn from_bytes_le(buf: &[u8]) -> Result<$t> {
+                let mut v = <$t>::zero();
+                v.set_little_endian_mod(buf)?;
+                let canonical = v.le_array()?;
+                let same = buf
+                    .iter()
+                    .enumerate()
+                    .all(|(i, b)| *b == canonical.get(i).copied().unwrap_or(0));
+                if !same {
+                    return Err(Error::InvalidEncoding);
+                }
+                Ok(v)
+            }
+            pub fn from_bytes_be(buf: &[u8]) -> Result<$t> {
+                let mut le = buf.to_vec();
+                le.reverse();
+                <$t>::from_bytes_le(&le)
+            }
+            pub fn to_u64_limbs(&self) -> Result<[u64; $limbs]> {
+                let buf = self.le_array()?;
+                let mut v = [0u64; $limbs];
+                for (v, c) in v.iter_mut().zip(buf.chunks(8)) {
+                    *v = u64::from_le_bytes(c.try_into().unwrap());
+                }
+                Ok(v)
+            }
+            pub fn from_u64_limbs(limbs: &[u64; $limbs]) -> Result<$t> {
+                let mut buf = [0u8; $n];
+                for (c, v) in buf.chunks_mut(8).zip(limbs.iter()) {
+                    c.copy_from_slice(&v.to_le_bytes());
+                }
+                <$t>::from_bytes_le(&buf)
+            }
+            // reduced modulo the modulus
+            pub fn from_u128(x: u128) -> $t {
+                assert_init();
+                let mut v = <$t>::zero();
+                v.set_little_endian_mod(&x.to_le_bytes())
+                    .expect("set_little_endian_mod");
+                v
+            }
+        }
+        // little endian, zero padded
+        impl TryFrom<&$t> for [u8; $n] {
+            type Error = Error;
+            fn try_from(x: &$t) -> Result<[u8; $n]> {
+                x.le_array()
+            }
+        }
+        impl TryFrom<[u8; $n]> for $t {
+            type Error = Error;
+            fn try_from(buf: [u8; $n]) -> Result<$t> {
+                <$t>::from_bytes_le(&buf)
+            }
+        }
+    };
+}
+
+// by-value and mixed variants of an operator implemented for `&$t op &$rhs`
+macro_rules! op_variants_impl {
+    ($t:ty, $rhs:ty, $op:ident, $op_fn:ident, $op_assign:ident, $op_assign_fn:ident) => {
+        impl $op<$rhs> for $t {
+            type Output = $t;
+            fn $op_fn(self, other: $rhs) -> $t {
+                <&$t as $op<&$rhs>>::$op_fn(&self, &other)
+            }
+        }
+        impl<'a> $op<&'a $rhs> for $t {
+            type Output = $t;
+            fn $op_fn(self, other: &$rhs) -> $t {
+                <&$t as $op<&$rhs>>::$op_fn(&self, other)
+            }
+        }
+        impl<'a> $op<$rhs> for &'a $t {
+            type Output = $t;
+            fn $op_fn(self, other: $rhs) -> $t {
+                <&$t as $op<&$rhs>>::$op_fn(self, &other)
+            }
+        }
+        impl $op_assign<$rhs> for $t {
+            fn $op_assign_fn(&mut self, other: $rhs) {
+                <$t as $op_assign<&$rhs>>::$op_assign_fn(self, &other)
             }
         }
     };
@@ -326,25 +910,43 @@ macro_rules! add_op_impl {
     ($t:ty, $add_fn:ident, $sub_fn:ident, $neg_fn:ident) => {
         impl $t {
             pub fn add(z: &mut $t, x: &$t, y: &$t) {
+                assert_init();
                 unsafe { $add_fn(z, x, y) }
             }
             pub fn sub(z: &mut $t, x: &$t, y: &$t) {
+                assert_init();
                 unsafe { $sub_fn(z, x, y) }
             }
             pub fn neg(y: &mut $t, x: &$t) {
+                assert_init();
                 unsafe { $neg_fn(y, x) }
             }
         }
+        impl<'a> Neg for &'a $t {
+            type Output = $t;
+            fn neg(self) -> $t {
+                let mut v = <$t>::zero();
+                <$t>::neg(&mut v, self);
+                v
+            }
+        }
+        impl Neg for $t {
+            type Output = $t;
+            fn neg(self) -> $t {
+                -&self
+            }
+        }
         impl<'a> Add for &'a $t {
             type Output = $t;
             fn add(self, other: &$t) -> $t {
-                let mut v = unsafe { <$t>::uninit() };
+                let mut v = <$t>::zero();
                 <$t>::add(&mut v, &self, &other);
                 v
             }
         }
         impl<'a> AddAssign<&'a $t> for $t {
             fn add_assign(&mut self, other: &$t) {
+                assert_init();
                 let z: *mut $t = self;
                 unsafe {
                     $add_fn(z, z as *const $t, other as *const $t);
@@ -354,19 +956,38 @@ macro_rules! add_op_impl {
         impl<'a> Sub for &'a $t {
             type Output = $t;
             fn sub(self, other: &$t) -> $t {
-                let mut v = unsafe { <$t>::uninit() };
+                let mut v = <$t>::zero();
                 <$t>::sub(&mut v, &self, &other);
                 v
             }
         }
         impl<'a> SubAssign<&'a $t> for $t {
             fn sub_assign(&mut self, other: &$t) {
+                assert_init();
                 let z: *mut $t = self;
                 unsafe {
                     $sub_fn(z, z as *const $t, other as *const $t);
                 }
             }
         }
+        op_variants_impl![$t, $t, Add, add, AddAssign, add_assign];
+        op_variants_impl![$t, $t, Sub, sub, SubAssign, sub_assign];
+        impl Sum for $t {
+            fn sum<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold(<$t>::zero(), |mut acc, x| {
+                    acc += &x;
+                    acc
+                })
+            }
+        }
+        impl<'a> Sum<&'a $t> for $t {
+            fn sum<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold(<$t>::zero(), |mut acc, x| {
+                    acc += x;
+                    acc
+                })
+            }
+        }
     };
 }
 
@@ -374,28 +995,46 @@ macro_rules! field_mul_op_impl {
     ($t:ty, $mul_fn:ident, $div_fn:ident, $inv_fn:ident, $sqr_fn:ident) => {
         impl $t {
             pub fn mul(z: &mut $t, x: &$t, y: &$t) {
+                assert_init();
                 unsafe { $mul_fn(z, x, y) }
             }
             pub fn div(z: &mut $t, x: &$t, y: &$t) {
+                assert_init();
                 unsafe { $div_fn(z, x, y) }
             }
             pub fn inv(y: &mut $t, x: &$t) {
+                assert_init();
                 unsafe { $inv_fn(y, x) }
             }
             pub fn sqr(y: &mut $t, x: &$t) {
+                assert_init();
                 unsafe { $sqr_fn(y, x) }
             }
+            pub fn square(&self) -> $t {
+                let mut v = <$t>::zero();
+                <$t>::sqr(&mut v, self);
+                v
+            }
+            pub fn inverse(&self) -> Option<$t> {
+                if self.is_zero() {
+                    return None;
+                }
+                let mut v = <$t>::zero();
+                <$t>::inv(&mut v, self);
+                Some(v)
+            }
         }
         impl<'a> Mul for &'a $t {
             type Output = $t;
             fn mul(self, other: &$t) -> $t {
-                let mut v = unsafe { <$t>::uninit() };
+                let mut v = <$t>::zero();
                 <$t>::mul(&mut v, &self, &other);
                 v
             }
         }
         impl<'a> MulAssign<&'a $t> for $t {
             fn mul_assign(&mut self, other: &$t) {
+                assert_init();
                 let z: *mut $t = self;
                 unsafe {
                     $mul_fn(z, z as *const $t, other as *const $t);
@@ -405,68 +1044,228 @@ macro_rules! field_mul_op_impl {
         impl<'a> Div for &'a $t {
             type Output = $t;
             fn div(self, other: &$t) -> $t {
-                let mut v = unsafe { <$t>::uninit() };
+                let mut v = <$t>::zero();
                 <$t>::div(&mut v, &self, &other);
                 v
             }
         }
         impl<'a> DivAssign<&'a $t> for $t {
             fn div_assign(&mut self, other: &$t) {
+                assert_init();
                 let z: *mut $t = self;
                 unsafe {
                     $div_fn(z, z as *const $t, other as *const $t);
                 }
             }
         }
+        op_variants_impl![$t, $t, Mul, mul, MulAssign, mul_assign];
+        op_variants_impl![$t, $t, Div, div, DivAssign, div_assign];
+        impl Product for $t {
+            fn product<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold(<$t>::one(), |mut acc, x| {
+                    acc *= &x;
+                    acc
+                })
+            }
+        }
+        impl<'a> Product<&'a $t> for $t {
+            fn product<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold(<$t>::one(), |mut acc, x| {
+                    acc *= x;
+                    acc
+                })
+            }
+        }
     };
 }
 
 macro_rules! ec_impl {
-    ($t:ty, $dbl_fn:ident, $mul_fn:ident, $normalize_fn:ident, $set_hash_and_map_fn:ident, $mul_vec_fn:ident) => {
+    ($t:ty, $dbl_fn:ident, $mul_fn:ident, $mul_ct_fn:ident, $normalize_fn:ident, $set_hash_and_map_fn:ident, $mul_vec_fn:ident, $mul_vec_mt_fn:ident, $is_valid_order_fn:ident, $set_dst_fn:ident, $hash_and_map_with_dst_fn:ident) => {
         impl $t {
+            pub(crate) fn check_order(&self) -> Result<()> {
+                init_check()?;
+                if unsafe { $is_valid_order_fn(self) } == 1 {
+                    Ok(())
+                } else {
+                    Err(Error::NotInSubgroup)
+                }
+            }
+            // is_valid only checks the curve equation
+            pub fn is_in_subgroup(&self) -> bool {
+                assert_init();
+                unsafe { $is_valid_order_fn(self) == 1 }
+            }
+            // checks the subgroup whatever the policy; self is unchanged on failure
+            pub fn deserialize_checked(&mut self, buf: &[u8]) -> Result<()> {
+                let mut v = <$t>::zero();
+                v.deserialize_raw(buf)?;
+                v.check_order()?;
+                *self = v;
+                Ok(())
+            }
+            // N is the serialized size on the current curve, see CurveType
+            pub fn to_compressed<const N: usize>(&self) -> Result<[u8; N]> {
+                let mut buf = [0u8; N];
+                if self.serialize_into(&mut buf)? != N {
+                    return Err(Error::InvalidLength);
+                }
+                Ok(buf)
+            }
+            // for trusted input only
+            pub fn deserialize_unchecked(&mut self, buf: &[u8]) -> Result<()> {
+                let mut v = <$t>::zero();
+                v.deserialize_raw(buf)?;
+                *self = v;
+                Ok(())
+            }
             pub fn dbl(y: &mut $t, x: &$t) {
+                assert_init();
                 unsafe { $dbl_fn(y, x) }
             }
             pub fn mul(z: &mut $t, x: &$t, y: &Fr) {
+                assert_init();
                 unsafe { $mul_fn(z, x, y) }
             }
+            // constant time in y, for secret scalars
+            pub fn mul_ct(z: &mut $t, x: &$t, y: &Fr) {
+                assert_init();
+                unsafe { $mul_ct_fn(z, x, y) }
+            }
             pub fn normalize(y: &mut $t, x: &$t) {
+                assert_init();
                 unsafe { $normalize_fn(y, x) }
             }
-            pub fn set_hash_of(&mut self, buf: &[u8]) -> bool {
-                unsafe { $set_hash_and_map_fn(self, buf.as_ptr(), buf.len()) == 0 }
+            pub fn set_hash_of(&mut self, buf: &[u8]) -> Result<()> {
+                init_check()?;
+                check(
+                    unsafe { $set_hash_and_map_fn(self, buf.as_ptr(), buf.len()) },
+                    Error::HashFailed,
+                )
+            }
+            // RFC 9380 hash_to_curve; needs MapToMode::HashToCurve
+            pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Result<$t> {
+                hash_to_curve_check()?;
+                let mut v = <$t>::zero();
+                check(
+                    unsafe {
+                        $hash_and_map_with_dst_fn(
+                            &mut v,
+                            msg.as_ptr(),
+                            msg.len(),
+                            dst.as_ptr(),
+                            dst.len(),
+                        )
+                    },
+                    Error::HashFailed,
+                )?;
+                Ok(v)
             }
-            pub fn mul_vec(z: &mut $t, x: &[$t], y: &[Fr]) {
+            // DST used by set_hash_of in MapToMode::HashToCurve
+            pub fn set_dst(dst: &[u8]) -> Result<()> {
+                init_check()?;
+                check(
+                    unsafe { $set_dst_fn(dst.as_ptr(), dst.len()) },
+                    Error::HashFailed,
+                )
+            }
+            pub fn mul_vec(z: &mut $t, x: &[$t], y: &[Fr]) -> Result<()> {
+                init_check()?;
+                if x.len() != y.len() {
+                    return Err(Error::InvalidLength);
+                }
                 unsafe { $mul_vec_fn(z, x.as_ptr(), y.as_ptr(), x.len()) }
+                Ok(())
+            }
+            // mul_vec on cpu_n threads, or on all cores if cpu_n is 0; libmcl
+            // built without OpenMP uses one thread
+            pub fn mul_vec_mt(z: &mut $t, x: &[$t], y: &[Fr], cpu_n: usize) -> Result<()> {
+                init_check()?;
+                if x.len() != y.len() {
+                    return Err(Error::InvalidLength);
+                }
+                unsafe { $mul_vec_mt_fn(z, x.as_ptr(), y.as_ptr(), x.len(), cpu_n) }
+                Ok(())
+            }
+            pub fn double(&self) -> $t {
+                let mut v = <$t>::zero();
+                <$t>::dbl(&mut v, self);
+                v
+            }
+            pub fn mul_scalar(&self, y: &Fr) -> $t {
+                let mut v = <$t>::zero();
+                <$t>::mul(&mut v, self, y);
+                v
+            }
+            pub fn mul_scalar_ct(&self, y: &Fr) -> $t {
+                let mut v = <$t>::zero();
+                <$t>::mul_ct(&mut v, self, y);
+                v
+            }
+            pub fn normalized(&self) -> $t {
+                let mut v = <$t>::zero();
+                <$t>::normalize(&mut v, self);
+                v
+            }
+            // sum of x[i] * y[i]
+            pub fn msm(x: &[$t], y: &[Fr]) -> Result<$t> {
+                let mut v = <$t>::zero();
+                <$t>::mul_vec(&mut v, x, y)?;
+                Ok(v)
+            }
+            pub fn msm_mt(x: &[$t], y: &[Fr], cpu_n: usize) -> Result<$t> {
+                let mut v = <$t>::zero();
+                <$t>::mul_vec_mt(&mut v, x, y, cpu_n)?;
+                Ok(v)
             }
         }
+        impl<'a, 'b> Mul<&'b Fr> for &'a $t {
+            type Output = $t;
+            fn mul(self, other: &Fr) -> $t {
+                self.mul_scalar(other)
+            }
+        }
+        impl<'a> MulAssign<&'a Fr> for $t {
+            fn mul_assign(&mut self, other: &Fr) {
+                assert_init();
+                let z: *mut $t = self;
+                unsafe {
+                    $mul_fn(z, z as *const $t, other as *const Fr);
+                }
+            }
+        }
+        op_variants_impl![$t, Fr, Mul, mul, MulAssign, mul_assign];
     };
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Copy, Clone)]
 #[repr(C)]
 pub struct Fp {
     d: [u64; MCLBN_FP_UNIT_SIZE],
 }
 impl Fp {
-    pub fn get_order() -> String {
+    pub fn get_order() -> Result<String> {
         get_field_order()
     }
+    // the map used by set_hash_of under the current MapToMode
+    pub fn map_to_g1(&self) -> Result<G1> {
+        init_check()?;
+        let mut v = G1::zero();
+        check(unsafe { mclBnFp_mapToG1(&mut v, self) }, Error::HashFailed)?;
+        Ok(v)
+    }
 }
 common_impl![Fp, mclBnFp_isEqual, mclBnFp_isZero];
 is_valid_impl![Fp, mclBnFp_isValid];
-serialize_impl![
-    Fp,
-    mclBn_getFpByteSize(),
-    mclBnFp_serialize,
-    mclBnFp_deserialize
-];
-str_impl![Fp, 128, mclBnFp_getStr, mclBnFp_setStr];
+serialize_impl![Fp, 48, mclBnFp_serialize, mclBnFp_deserialize];
+hash_impl![Fp];
+debug_impl![Fp];
+str_impl![Fp, 512, mclBnFp_getStr, mclBnFp_setStr];
 int_impl![Fp, mclBnFp_setInt32, mclBnFp_isOne];
 base_field_impl![
     Fp,
     mclBnFp_setLittleEndian,
     mclBnFp_setLittleEndianMod,
+    mclBnFp_getLittleEndian,
     mclBnFp_setHashOf,
     mclBnFp_setByCSPRNG,
     mclBnFp_isOdd,
@@ -474,53 +1273,72 @@ base_field_impl![
     mclBnFp_cmp,
     mclBnFp_squareRoot
 ];
+bytes_impl![Fp, 48, 6, get_fp_serialized_size, mclBnFp_setBigEndianMod];
 add_op_impl![Fp, mclBnFp_add, mclBnFp_sub, mclBnFp_neg];
 field_mul_op_impl![Fp, mclBnFp_mul, mclBnFp_div, mclBnFp_inv, mclBnFp_sqr];
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Copy, Clone)]
 #[repr(C)]
 pub struct Fp2 {
     pub d: [Fp; 2],
 }
 common_impl![Fp2, mclBnFp2_isEqual, mclBnFp2_isZero];
-serialize_impl![
-    Fp2,
-    mclBn_getFpByteSize() * 2,
-    mclBnFp2_serialize,
-    mclBnFp2_deserialize
-];
+serialize_impl![Fp2, 96, mclBnFp2_serialize, mclBnFp2_deserialize];
+hash_impl![Fp2];
 add_op_impl![Fp2, mclBnFp2_add, mclBnFp2_sub, mclBnFp2_neg];
 field_mul_op_impl![Fp2, mclBnFp2_mul, mclBnFp2_div, mclBnFp2_inv, mclBnFp2_sqr];
 impl Fp2 {
-    pub fn square_root(y: &mut Fp2, x: &Fp2) -> bool {
-        unsafe { mclBnFp2_squareRoot(y, x) == 0 }
+    pub fn one() -> Fp2 {
+        Fp2 {
+            d: [Fp::one(), Fp::zero()],
+        }
+    }
+    pub fn square_root(y: &mut Fp2, x: &Fp2) -> Result<()> {
+        init_check()?;
+        check(unsafe { mclBnFp2_squareRoot(y, x) }, Error::NoSquareRoot)
+    }
+    pub fn sqrt(&self) -> Result<Fp2> {
+        let mut v = Fp2::zero();
+        Fp2::square_root(&mut v, self)?;
+        Ok(v)
+    }
+    pub fn map_to_g2(&self) -> Result<G2> {
+        init_check()?;
+        let mut v = G2::zero();
+        check(unsafe { mclBnFp2_mapToG2(&mut v, self) }, Error::HashFailed)?;
+        Ok(v)
     }
 }
 
-#[derive(Default, Debug, Clone)]
+// Fr holds secret keys, so it is not Copy; implicit copies are not wiped by
+// zeroize
+#[derive(Default, Clone)]
 #[repr(C)]
 pub struct Fr {
     d: [u64; MCLBN_FR_UNIT_SIZE],
 }
 impl Fr {
-    pub fn get_order() -> String {
+    pub fn get_order() -> Result<String> {
         get_curve_order()
     }
 }
 common_impl![Fr, mclBnFr_isEqual, mclBnFr_isZero];
 is_valid_impl![Fr, mclBnFr_isValid];
-serialize_impl![
-    Fr,
-    mclBn_getFrByteSize(),
-    mclBnFr_serialize,
-    mclBnFr_deserialize
-];
-str_impl![Fr, 128, mclBnFr_getStr, mclBnFr_setStr];
+serialize_impl![Fr, 32, mclBnFr_serialize, mclBnFr_deserialize];
+hash_impl![Fr];
+str_impl![Fr, 512, mclBnFr_getStr, mclBnFr_setStr];
+// Fr is used for secret keys; keep its value out of logs
+impl fmt::Debug for Fr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Fr(<redacted>)")
+    }
+}
 int_impl![Fr, mclBnFr_setInt32, mclBnFr_isOne];
 base_field_impl![
     Fr,
     mclBnFr_setLittleEndian,
     mclBnFr_setLittleEndianMod,
+    mclBnFr_getLittleEndian,
     mclBnFr_setHashOf,
     mclBnFr_setByCSPRNG,
     mclBnFr_isOdd,
@@ -528,10 +1346,11 @@ base_field_impl![
     mclBnFr_cmp,
     mclBnFr_squareRoot
 ];
+bytes_impl![Fr, 32, 4, get_fr_serialized_size, mclBnFr_setBigEndianMod];
 add_op_impl![Fr, mclBnFr_add, mclBnFr_sub, mclBnFr_neg];
 field_mul_op_impl![Fr, mclBnFr_mul, mclBnFr_div, mclBnFr_inv, mclBnFr_sqr];
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Copy, Clone)]
 #[repr(C)]
 pub struct G1 {
     pub x: Fp,
@@ -542,22 +1361,36 @@ common_impl![G1, mclBnG1_isEqual, mclBnG1_isZero];
 is_valid_impl![G1, mclBnG1_isValid];
 serialize_impl![
     G1,
-    mclBn_getFpByteSize(),
+    48,
     mclBnG1_serialize,
-    mclBnG1_deserialize
+    mclBnG1_deserialize,
+    G1::policy_check
+];
+hash_impl![G1, ec];
+debug_impl![G1];
+str_impl![
+    G1,
+    512 * 3,
+    mclBnG1_getStr,
+    mclBnG1_setStr,
+    G1::policy_check
 ];
-str_impl![G1, 128 * 3, mclBnG1_getStr, mclBnG1_setStr];
 add_op_impl![G1, mclBnG1_add, mclBnG1_sub, mclBnG1_neg];
 ec_impl![
     G1,
     mclBnG1_dbl,
     mclBnG1_mul,
+    mclBnG1_mulCT,
     mclBnG1_normalize,
     mclBnG1_hashAndMapTo,
-    mclBnG1_mulVec
+    mclBnG1_mulVec,
+    mclBnG1_mulVecMT,
+    mclBnG1_isValidOrder,
+    mclBnG1_setDst,
+    mclBnG1_hashAndMapToWithDst
 ];
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Copy, Clone)]
 #[repr(C)]
 pub struct G2 {
     pub x: Fp2,
@@ -568,22 +1401,36 @@ common_impl![G2, mclBnG2_isEqual, mclBnG2_isZero];
 is_valid_impl![G2, mclBnG2_isValid];
 serialize_impl![
     G2,
-    mclBn_getFpByteSize() * 2,
+    96,
     mclBnG2_serialize,
-    mclBnG2_deserialize
+    mclBnG2_deserialize,
+    G2::policy_check
+];
+hash_impl![G2, ec];
+debug_impl![G2];
+str_impl![
+    G2,
+    512 * 3 * 2,
+    mclBnG2_getStr,
+    mclBnG2_setStr,
+    G2::policy_check
 ];
-str_impl![G2, 128 * 3 * 2, mclBnG2_getStr, mclBnG2_setStr];
 add_op_impl![G2, mclBnG2_add, mclBnG2_sub, mclBnG2_neg];
 ec_impl![
     G2,
     mclBnG2_dbl,
     mclBnG2_mul,
+    mclBnG2_mulCT,
     mclBnG2_normalize,
     mclBnG2_hashAndMapTo,
-    mclBnG2_mulVec
+    mclBnG2_mulVec,
+    mclBnG2_mulVecMT,
+    mclBnG2_isValidOrder,
+    mclBnG2_setDst,
+    mclBnG2_hashAndMapToWithDst
 ];
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Copy, Clone)]
 #[repr(C)]
 pub struct GT {
     d: [Fp; 12],
@@ -591,34 +1438,527 @@ pub struct GT {
 common_impl![GT, mclBnGT_isEqual, mclBnGT_isZero];
 serialize_impl![
     GT,
-    mclBn_getFpByteSize() * 12,
+    48 * 12,
     mclBnGT_serialize,
-    mclBnGT_deserialize
+    mclBnGT_deserialize,
+    GT::policy_check
+];
+hash_impl![GT];
+debug_impl![GT];
+str_impl![
+    GT,
+    512 * 12,
+    mclBnGT_getStr,
+    mclBnGT_setStr,
+    GT::policy_check
 ];
-str_impl![GT, 128 * 12, mclBnGT_getStr, mclBnGT_setStr];
 int_impl![GT, mclBnGT_setInt32, mclBnGT_isOne];
 add_op_impl![GT, mclBnGT_add, mclBnGT_sub, mclBnGT_neg];
 field_mul_op_impl![GT, mclBnGT_mul, mclBnGT_div, mclBnGT_inv, mclBnGT_sqr];
 impl GT {
     pub fn pow(z: &mut GT, x: &GT, y: &Fr) {
+        assert_init();
         unsafe { mclBnGT_pow(z, x, y) }
     }
+    pub fn pow_scalar(&self, y: &Fr) -> GT {
+        let mut v = GT::zero();
+        GT::pow(&mut v, self, y);
+        v
+    }
+    // best effort for secret exponents, not verified constant time: libmcl
+    // has no constant time GT pow, so this is a Montgomery ladder over every
+    // bit of y with masked swaps, and it is only as constant time as libmcl's
+    // GT mul and sqr and the compiler allow
+    pub fn pow_ladder(z: &mut GT, x: &GT, y: &Fr) {
+        assert_init();
+        let mut buf = [0u8; MCLBN_FR_UNIT_SIZE * 8];
+        y.write_little_endian(&mut buf)
+            .expect("write_little_endian");
+        let mut r0 = GT::one();
+        let mut r1 = *x;
+        let mut t = GT::zero();
+        let mut swap = 0;
+        for i in (0..MCLBN_FR_UNIT_SIZE * 64).rev() {
+            let bit = u64::from((buf[i / 8] >> (i % 8)) & 1);
+            gt_cswap(&mut r0, &mut r1, swap ^ bit);
+            swap = bit;
+            GT::mul(&mut t, &r0, &r1);
+            r1 = t;
+            GT::sqr(&mut t, &r0);
+            r0 = t;
+        }
+        gt_cswap(&mut r0, &mut r1, swap);
+        *z = r0;
+    }
+    pub fn pow_scalar_ladder(&self, y: &Fr) -> GT {
+        let mut v = GT::zero();
+        GT::pow_ladder(&mut v, self, y);
+        v
+    }
+    // x^r = 1, computed as x^(r-1) x since r is zero in Fr; pow assumes
+    // its input is already in GT, powGeneric does not
+    pub fn is_in_subgroup(&self) -> bool {
+        if self.is_zero() {
+            return false;
+        }
+        let mut v = GT::zero();
+        unsafe { mclBnGT_powGeneric(&mut v, self, &Fr::from_int(-1)) }
+        (v * self).is_one()
+    }
+    pub(crate) fn check_order(&self) -> Result<()> {
+        init_check()?;
+        if self.is_in_subgroup() {
+            Ok(())
+        } else {
+            Err(Error::NotInSubgroup)
+        }
+    }
+    // the subgroup check deserialize does unless set_verify_order_gt is off
+    fn policy_check(&self) -> Result<()> {
+        if VERIFY_ORDER_GT.load(Ordering::Acquire) {
+            self.check_order()
+        } else {
+            Ok(())
+        }
+    }
+    // checks the subgroup whatever the policy; self is unchanged on failure
+    pub fn deserialize_checked(&mut self, buf: &[u8]) -> Result<()> {
+        let mut v = GT::zero();
+        v.deserialize_raw(buf)?;
+        v.check_order()?;
+        *self = v;
+        Ok(())
+    }
+    // any element of Fp12, e.g. a Miller loop output before final_exp
+    pub fn deserialize_unchecked(&mut self, buf: &[u8]) -> Result<()> {
+        let mut v = GT::zero();
+        v.deserialize_raw(buf)?;
+        *self = v;
+        Ok(())
+    }
+}
+
+// swaps a and b if bit is 1, without branching on bit
+fn gt_cswap(a: &mut GT, b: &mut GT, bit: u64) {
+    let mask = core::hint::black_box(0u64.wrapping_sub(bit));
+    for (a, b) in a.d.iter_mut().zip(b.d.iter_mut()) {
+        for (a, b) in a.d.iter_mut().zip(b.d.iter_mut()) {
+            let t = (*a ^ *b) & mask;
+            *a ^= t;
+            *b ^= t;
+        }
+    }
+}
+
+// EIP-2537 field elements: 64 bytes big endian, Fp2 as c0 || c1
+const EIP2537_FP_SIZE: usize = 64;
+
+fn eip2537_check() -> Result<()> {
+    init_check()?;
+    if get_curve_type() != Some(CurveType::BLS12_381) {
+        return Err(Error::Unsupported);
+    }
+    Ok(())
+}
+
+impl Fp {
+    fn eip2537_write(&self, out: &mut [u8]) -> Result<()> {
+        let b = self.to_bytes_be()?;
+        let (pad, v) = out.split_at_mut(EIP2537_FP_SIZE - b.len());
+        pad.fill(0);
+        v.copy_from_slice(&b);
+        Ok(())
+    }
+    // rejects non-zero padding and values not less than p
+    fn eip2537_read(buf: &[u8]) -> Result<Fp> {
+        Fp::from_bytes_be(buf)
+    }
+}
+
+impl Fp2 {
+    fn eip2537_write(&self, out: &mut [u8]) -> Result<()> {
+        let (c0, c1) = out.split_at_mut(EIP2537_FP_SIZE);
+        self.d[0].eip2537_write(c0)?;
+        self.d[1].eip2537_write(c1)
+    }
+    fn eip2537_read(buf: &[u8]) -> Result<Fp2> {
+        let (c0, c1) = buf.split_at(EIP2537_FP_SIZE);
+        Ok(Fp2 {
+            d: [Fp::eip2537_read(c0)?, Fp::eip2537_read(c1)?],
+        })
+    }
+}
+
+// to_uncompressed is mcl's x || y: each coordinate serialized like Fp or Fp2,
+// so its width and byte order follow the curve and the serialization mode.
+// to_eip2537 is the fixed layout of the EIP-2537 precompiles, BLS12-381 only.
+// Both are all zero for the identity, since (0, 0) is on none of the curves
+macro_rules! affine_impl {
+    ($t:ident, $base:ty, $verify_order:ident, $fp_count:expr) => {
+        impl $t {
+            // the subgroup check deserialize does unless set_verify_order_* is off
+            fn policy_check(&self) -> Result<()> {
+                if $verify_order.load(Ordering::Acquire) {
+                    self.check_order()
+                } else {
+                    Ok(())
+                }
+            }
+            // None for the identity
+            pub fn to_affine(&self) -> Option<($base, $base)> {
+                if self.is_zero() {
+                    return None;
+                }
+                let p = self.normalized();
+                Some((p.x, p.y))
+            }
+            // checks that (x, y) is on the curve but not the subgroup
+            pub fn from_affine(x: &$base, y: &$base) -> Result<$t> {
+                init_check()?;
+                let v = $t {
+                    x: *x,
+                    y: *y,
+                    z: <$base>::one(),
+                };
+                if !v.is_valid() {
+                    return Err(Error::InvalidEncoding);
+                }
+                Ok(v)
+            }
+            // returns the number of bytes written
+            pub fn to_uncompressed_into(&self, out: &mut [u8]) -> Result<usize> {
+                init_check()?;
+                let size = get_fp_serialized_size() as usize * $fp_count;
+                if out.len() < size * 2 {
+                    return Err(Error::BufferTooSmall);
+                }
+                let out = &mut out[..size * 2];
+                match self.to_affine() {
+                    None => out.fill(0),
+                    Some((x, y)) => {
+                        let (ox, oy) = out.split_at_mut(size);
+                        x.serialize_into(ox)?;
+                        y.serialize_into(oy)?;
+                    }
+                }
+                Ok(size * 2)
+            }
+            pub fn to_uncompressed(&self) -> Result<Vec<u8>> {
+                let mut buf = [0u8; <$t>::MAX_SERIALIZED_SIZE * 2];
+                let n = self.to_uncompressed_into(&mut buf)?;
+                Ok(buf[..n].to_vec())
+            }
+            // subject to the same subgroup check as deserialize
+            pub fn from_uncompressed(buf: &[u8]) -> Result<$t> {
+                let v = <$t>::from_uncompressed_unchecked(buf)?;
+                v.policy_check()?;
+                Ok(v)
+            }
+            // on the curve but not necessarily in the subgroup
+            pub fn from_uncompressed_unchecked(buf: &[u8]) -> Result<$t> {
+                init_check()?;
+                let size = get_fp_serialized_size() as usize * $fp_count;
+                if buf.len() != size * 2 {
+                    return Err(Error::InvalidEncoding);
+                }
+                if buf.iter().all(|&b| b == 0) {
+                    return Ok(<$t>::zero());
+                }
+                let mut x = <$base>::zero();
+                let mut y = <$base>::zero();
+                x.deserialize(&buf[..size])?;
+                y.deserialize(&buf[size..])?;
+                <$t>::from_affine(&x, &y)
+            }
+            // independent of the serialization mode; Unsupported on curves
+            // other than BLS12-381
+            // returns the number of bytes written
+            pub fn to_eip2537_into(&self, out: &mut [u8]) -> Result<usize> {
+                eip2537_check()?;
+                let size = EIP2537_FP_SIZE * $fp_count;
+                if out.len() < size * 2 {
+                    return Err(Error::BufferTooSmall);
+                }
+                let out = &mut out[..size * 2];
+                match self.to_affine() {
+                    None => out.fill(0),
+                    Some((x, y)) => {
+                        let (ox, oy) = out.split_at_mut(size);
+                        x.eip2537_write(ox)?;
+                        y.eip2537_write(oy)?;
+                    }
+                }
+                Ok(size * 2)
+            }
+            pub fn to_eip2537(&self) -> Result<Vec<u8>> {
+                let mut v = vec![0u8; EIP2537_FP_SIZE * $fp_count * 2];
+                let n = self.to_eip2537_into(&mut v)?;
+                v.truncate(n);
+                Ok(v)
+            }
+            // subject to the same subgroup check as deserialize
+            pub fn from_eip2537(buf: &[u8]) -> Result<$t> {
+                let v = <$t>::from_eip2537_unchecked(buf)?;
+                v.policy_check()?;
+                Ok(v)
+            }
+            // on the curve but not necessarily in the subgroup
+            pub fn from_eip2537_unchecked(buf: &[u8]) -> Result<$t> {
+                eip2537_check()?;
+                let size = EIP2537_FP_SIZE * $fp_count;
+                if buf.len() != size * 2 {
+                    return Err(Error::InvalidEncoding);
+                }
+                if buf.iter().all(|&b| b == 0) {
+                    return Ok(<$t>::zero());
+                }
+                let x = <$base>::eip2537_read(&buf[..size])?;
+                let y = <$base>::eip2537_read(&buf[size..])?;
+                <$t>::from_affine(&x, &y)
+            }
+        }
+    };
+}
+
+affine_impl![G1, Fp, VERIFY_ORDER_G1, 1];
+affine_impl![G2, Fp2, VERIFY_ORDER_G2, 2];
+
+macro_rules! generator_impl {
+    ($t:ty, $get_base_point_fn:ident, $set_generator_fn:ident) => {
+        impl $t {
+            // the base point libmcl uses, which is the one set by
+            // set_generator or else the curve's standard generator
+            pub fn generator() -> Result<$t> {
+                init_check()?;
+                let mut v = <$t>::zero();
+                unsafe { $get_base_point_fn(&mut v) };
+                Ok(v)
+            }
+            // installs g in libmcl until the next init; InvalidEncoding for
+            // zero or a point off the curve, NotInSubgroup for one outside the
+            // subgroup
+            pub fn set_generator(g: &$t) -> Result<()> {
+                init_check()?;
+                if g.is_zero() || !g.is_valid() {
+                    return Err(Error::InvalidEncoding);
+                }
+                g.check_order()?;
+                unsafe { $set_generator_fn(g) };
+                Ok(())
+            }
+        }
+    };
 }
 
+generator_impl![G1, mclBnG1_getBasePoint, mclBn_setGeneratorOfG1];
+generator_impl![G2, mclBnG2_getBasePoint, mclBn_setGeneratorOfG2];
+
+/// Line functions of a fixed `G2` point, for `precomputed_miller_loop`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct G2Prepared {
+    curve: CurveType,
+    buf: Vec<u64>,
+}
+
+impl G2Prepared {
+    pub fn new(q: &G2) -> Result<G2Prepared> {
+        init_check()?;
+        let curve = get_curve_type().ok_or(Error::NotInitialized)?;
+        let n = unsafe { mclBn_getUint64NumToPrecompute() } as usize;
+        let mut buf = vec![0u64; n];
+        unsafe { mclBn_precomputeG2(buf.as_mut_ptr(), q) }
+        Ok(G2Prepared { curve, buf })
+    }
+    // the table layout depends on the curve, so CurveMismatch after init
+    // with another one
+    fn check(&self) -> Result<()> {
+        init_check()?;
+        if get_curve_type() != Some(self.curve) {
+            return Err(Error::CurveMismatch);
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&G2> for G2Prepared {
+    type Error = Error;
+    fn try_from(q: &G2) -> Result<G2Prepared> {
+        G2Prepared::new(q)
+    }
+}
+
+impl TryFrom<G2> for G2Prepared {
+    type Error = Error;
+    fn try_from(q: G2) -> Result<G2Prepared> {
+        G2Prepared::new(&q)
+    }
+}
+
+// libmcl rejects zero and repeated ids without saying which
+fn check_ids(ids: &[Fr]) -> Result<()> {
+    if ids.iter().any(Fr::is_zero) {
+        return Err(Error::ZeroId);
+    }
+    let mut sorted = ids.to_vec();
+    sorted.sort();
+    if sorted.windows(2).any(|w| w[0] == w[1]) {
+        return Err(Error::DuplicateId);
+    }
+    Ok(())
+}
+
+macro_rules! polynomial_impl {
+    ($t:ty, $eval_fn:ident, $lagrange_fn:ident) => {
+        impl $t {
+            // c[0] + c[1] x + c[2] x^2 + ..., zero for an empty c
+            pub fn eval_polynomial(c: &[$t], x: &Fr) -> Result<$t> {
+                init_check()?;
+                let mut v = <$t>::zero();
+                if c.is_empty() {
+                    return Ok(v);
+                }
+                check(
+                    unsafe { $eval_fn(&mut v, c.as_ptr(), c.len(), x) },
+                    Error::InvalidLength,
+                )?;
+                Ok(v)
+            }
+            // f(0) for the polynomial f of degree < ids.len() with f(ids[i]) = ys[i]
+            pub fn lagrange_interpolation(ids: &[Fr], ys: &[$t]) -> Result<$t> {
+                init_check()?;
+                if ids.is_empty() || ids.len() != ys.len() {
+                    return Err(Error::InvalidLength);
+                }
+                check_ids(ids)?;
+                let mut v = <$t>::zero();
+                check(
+                    unsafe { $lagrange_fn(&mut v, ids.as_ptr(), ys.as_ptr(), ids.len()) },
+                    Error::DuplicateId,
+                )?;
+                Ok(v)
+            }
+        }
+    };
+}
+
+polynomial_impl![
+    Fr,
+    mclBn_FrEvaluatePolynomial,
+    mclBn_FrLagrangeInterpolation
+];
+polynomial_impl![
+    G1,
+    mclBn_G1EvaluatePolynomial,
+    mclBn_G1LagrangeInterpolation
+];
+polynomial_impl![
+    G2,
+    mclBn_G2EvaluatePolynomial,
+    mclBn_G2LagrangeInterpolation
+];
+
 pub fn get_version() -> u32 {
     unsafe { mclBn_getVersion() }
 }
 
-pub fn init(curve: CurveType) -> bool {
-    unsafe { mclBn_init(curve as i32, MCLBN_COMPILED_TIME_VAR) == 0 }
+pub fn init(curve: CurveType) -> Result<()> {
+    CURVE_TYPE.store(-1, Ordering::Release);
+    MAP_TO_MODE.store(-1, Ordering::Release);
+    check(
+        unsafe { mclBn_init(curve as i32, MCLBN_COMPILED_TIME_VAR) },
+        Error::InitFailed,
+    )?;
+    unsafe {
+        mclBn_verifyOrderG1(0);
+        mclBn_verifyOrderG2(0);
+    }
+    CURVE_TYPE.store(curve as i32, Ordering::Release);
+    Ok(())
+}
+
+pub fn set_map_to_mode(mode: MapToMode) -> Result<()> {
+    init_check()?;
+    check(
+        unsafe { mclBn_setMapToMode(mode as i32) },
+        Error::Unsupported,
+    )?;
+    MAP_TO_MODE.store(mode as i32, Ordering::Release);
+    Ok(())
+}
+
+// big endian, ZCash compatible encoding of Fr, Fp, G1 and G2 for BLS12_381,
+// as used by Ethereum; a global setting like the curve
+pub fn set_eth_serialization(enable: bool) {
+    unsafe { mclBn_setETHserialization(enable as i32) }
+}
+
+pub fn get_eth_serialization() -> bool {
+    unsafe { mclBn_getETHserialization() == 1 }
+}
+
+// whether deserialize and set_str reject G1 points outside the prime order
+// subgroup; on by default
+pub fn set_verify_order_g1(enable: bool) {
+    VERIFY_ORDER_G1.store(enable, Ordering::Release)
+}
+
+pub fn get_verify_order_g1() -> bool {
+    VERIFY_ORDER_G1.load(Ordering::Acquire)
+}
+
+pub fn set_verify_order_g2(enable: bool) {
+    VERIFY_ORDER_G2.store(enable, Ordering::Release)
+}
+
+pub fn get_verify_order_g2() -> bool {
+    VERIFY_ORDER_G2.load(Ordering::Acquire)
+}
+
+// GT is the order r subgroup of Fp12^*; turn this off to deserialize Miller
+// loop outputs
+pub fn set_verify_order_gt(enable: bool) {
+    VERIFY_ORDER_GT.store(enable, Ordering::Release)
+}
+
+pub fn get_verify_order_gt() -> bool {
+    VERIFY_ORDER_GT.load(Ordering::Acquire)
+}
+
+pub fn is_initialized() -> bool {
+    CURVE_TYPE.load(Ordering::Acquire) >= 0
+}
+
+pub fn get_curve_type() -> Option<CurveType> {
+    CurveType::from_i32(CURVE_TYPE.load(Ordering::Acquire))
+}
+
+/// Proof that `init` has succeeded; obtained from `Mcl::init` or `Mcl::get`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Mcl {
+    curve: CurveType,
+}
+
+impl Mcl {
+    pub fn init(curve: CurveType) -> Result<Mcl> {
+        init(curve)?;
+        Ok(Mcl { curve })
+    }
+    pub fn get() -> Result<Mcl> {
+        get_curve_type()
+            .map(|curve| Mcl { curve })
+            .ok_or(Error::NotInitialized)
+    }
+    pub fn curve_type(&self) -> CurveType {
+        self.curve
+    }
 }
 
 pub fn get_fr_serialized_size() -> u32 {
-    unsafe { mclBn_getFrByteSize() as u32 }
+    unsafe { mclBn_getFrByteSize() }
 }
 
 pub fn get_fp_serialized_size() -> u32 {
-    unsafe { mclBn_getFpByteSize() as u32 }
+    unsafe { mclBn_getFpByteSize() }
 }
 
 pub fn get_g1_serialized_size() -> u32 {
@@ -635,40 +1975,116 @@ pub fn get_gt_serialized_size() -> u32 {
 
 macro_rules! get_str_impl {
     ($get_str_fn:ident) => {{
-        let u = MaybeUninit::<[u8; 256]>::uninit();
-        let mut buf = unsafe { u.assume_init() };
+        let mut buf = [0u8; 256];
         let n: usize;
         unsafe {
             n = $get_str_fn(buf.as_mut_ptr(), buf.len());
         }
         if n == 0 {
-            panic!("get_str");
+            return Err(Error::BufferTooSmall);
         }
-        unsafe { core::str::from_utf8_unchecked(&buf[0..n]).into() }
+        Ok(unsafe { core::str::from_utf8_unchecked(&buf[0..n]).into() })
     }};
 }
 
-pub fn get_field_order() -> String {
+pub fn get_field_order() -> Result<String> {
+    init_check()?;
     get_str_impl![mclBn_getFieldOrder]
 }
 
-pub fn get_curve_order() -> String {
+pub fn get_curve_order() -> Result<String> {
+    init_check()?;
     get_str_impl![mclBn_getCurveOrder]
 }
 
 pub fn pairing(z: &mut GT, x: &G1, y: &G2) {
+    assert_init();
     unsafe {
         mclBn_pairing(z, x, y);
     }
 }
 
 pub fn miller_loop(z: &mut GT, x: &G1, y: &G2) {
+    assert_init();
     unsafe {
         mclBn_millerLoop(z, x, y);
     }
 }
 
+// product of miller_loop(x[i], y[i])
+pub fn multi_miller_loop(z: &mut GT, x: &[G1], y: &[G2]) -> Result<()> {
+    init_check()?;
+    if x.len() != y.len() {
+        return Err(Error::InvalidLength);
+    }
+    if x.is_empty() {
+        z.set_int(1);
+        return Ok(());
+    }
+    unsafe {
+        mclBn_millerLoopVec(z, x.as_ptr(), y.as_ptr(), x.len());
+    }
+    Ok(())
+}
+
+// multi_miller_loop on cpu_n threads, or on all cores if cpu_n is 0; libmcl
+// built without OpenMP uses one thread
+pub fn multi_miller_loop_mt(z: &mut GT, x: &[G1], y: &[G2], cpu_n: usize) -> Result<()> {
+    init_check()?;
+    if x.len() != y.len() {
+        return Err(Error::InvalidLength);
+    }
+    if x.is_empty() {
+        z.set_int(1);
+        return Ok(());
+    }
+    unsafe {
+        mclBn_millerLoopVecMT(z, x.as_ptr(), y.as_ptr(), x.len(), cpu_n);
+    }
+    Ok(())
+}
+
+// product of pairing(x[i], y[i]) with a single final exponentiation
+pub fn multi_pairing(x: &[G1], y: &[G2]) -> Result<GT> {
+    let mut f = GT::zero();
+    multi_miller_loop(&mut f, x, y)?;
+    let mut v = GT::zero();
+    final_exp(&mut v, &f);
+    Ok(v)
+}
+
+// whether the product of the pairings is one; false before init
+pub fn pairing_check(terms: &[(G1, G2)]) -> bool {
+    let (x, y): (Vec<G1>, Vec<G2>) = terms.iter().copied().unzip();
+    matches!(multi_pairing(&x, &y), Ok(e) if e.is_one())
+}
+
+pub fn precomputed_miller_loop(z: &mut GT, x: &G1, y: &G2Prepared) -> Result<()> {
+    y.check()?;
+    unsafe {
+        mclBn_precomputedMillerLoop(z, x, y.buf.as_ptr());
+    }
+    Ok(())
+}
+
+// miller_loop(x1, y1) * miller_loop(x2, y2)
+pub fn precomputed_miller_loop2(
+    z: &mut GT,
+    x1: &G1,
+    y1: &G2Prepared,
+    x2: &G1,
+    y2: &G2Prepared,
+) -> Result<()> {
+    y1.check()?;
+    y2.check()?;
+    unsafe {
+        mclBn_precomputedMillerLoop2(z, x1, y1.buf.as_ptr(), x2, y2.buf.as_ptr());
+    }
+    Ok(())
+}
+
 pub fn final_exp(y: &mut GT, x: &GT) {
+    assert_init();
     unsafe {
         mclBn_finalExp(y, x);
     }
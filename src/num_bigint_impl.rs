@@ -0,0 +1,37 @@
+// conversions between Fr, Fp and BigUint via the canonical little endian bytes
+use crate::{Error, Fp, Fr, Result};
+use core::convert::TryFrom;
+use num_bigint::BigUint;
+
+macro_rules! biguint_impl {
+    ($t:ty) => {
+        impl TryFrom<&$t> for BigUint {
+            type Error = Error;
+            fn try_from(x: &$t) -> Result<BigUint> {
+                Ok(BigUint::from_bytes_le(&x.to_bytes_le()?))
+            }
+        }
+        impl TryFrom<$t> for BigUint {
+            type Error = Error;
+            fn try_from(x: $t) -> Result<BigUint> {
+                BigUint::try_from(&x)
+            }
+        }
+        // fails with InvalidEncoding unless x is less than the modulus
+        impl TryFrom<&BigUint> for $t {
+            type Error = Error;
+            fn try_from(x: &BigUint) -> Result<$t> {
+                <$t>::from_bytes_le(&x.to_bytes_le())
+            }
+        }
+        impl TryFrom<BigUint> for $t {
+            type Error = Error;
+            fn try_from(x: BigUint) -> Result<$t> {
+                <$t>::try_from(&x)
+            }
+        }
+    };
+}
+
+biguint_impl![Fr];
+biguint_impl![Fp];
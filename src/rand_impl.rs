@@ -0,0 +1,86 @@
+use crate::{assert_init, mclBn_setRandFunc, Fp, Fr, G1, G2};
+use crate::{get_fp_serialized_size, get_fr_serialized_size};
+use alloc::boxed::Box;
+use core::cell::UnsafeCell;
+use core::ffi::c_void;
+use core::hint::spin_loop;
+use core::slice;
+use core::sync::atomic::{AtomicBool, Ordering};
+use rand_core::{CryptoRng, RngCore};
+
+// twice the byte size of the modulus, so that the bias of the reduction is
+// negligible
+macro_rules! field_random_impl {
+    ($t:ty, $size_fn:ident) => {
+        impl $t {
+            pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> $t {
+                assert_init();
+                let n = $size_fn() as usize * 2;
+                let mut buf = [0u8; 96];
+                rng.fill_bytes(&mut buf[..n]);
+                let mut v = <$t>::zero();
+                v.set_little_endian_mod(&buf[..n])
+                    .expect("set_little_endian_mod");
+                v
+            }
+        }
+    };
+}
+
+// hash of random bytes, so nobody knows its discrete log
+macro_rules! ec_random_impl {
+    ($t:ty) => {
+        impl $t {
+            pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> $t {
+                assert_init();
+                let mut buf = [0u8; 64];
+                rng.fill_bytes(&mut buf);
+                let mut v = <$t>::zero();
+                v.set_hash_of(&buf).expect("set_hash_of");
+                v
+            }
+        }
+    };
+}
+
+field_random_impl![Fr, get_fr_serialized_size];
+field_random_impl![Fp, get_fp_serialized_size];
+ec_random_impl![G1];
+ec_random_impl![G2];
+
+// libmcl may ask for bytes from several threads at once
+struct RandSource {
+    lock: AtomicBool,
+    rng: UnsafeCell<Box<dyn RngCore + Send>>,
+}
+
+unsafe extern "C" fn read_rand(this: *mut c_void, buf: *mut c_void, size: u32) -> u32 {
+    let src = &*(this as *const RandSource);
+    while src
+        .lock
+        .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+        .is_err()
+    {
+        spin_loop();
+    }
+    (*src.rng.get()).fill_bytes(slice::from_raw_parts_mut(buf as *mut u8, size as usize));
+    src.lock.store(false, Ordering::Release);
+    size
+}
+
+/// Makes libmcl draw all its randomness (`set_by_csprng` and friends) from `rng`.
+///
+/// A previously installed RNG is leaked rather than dropped, since another
+/// thread may still be reading from it.
+pub fn set_rand_func<R: RngCore + CryptoRng + Send + 'static>(rng: R) {
+    let src = Box::new(RandSource {
+        lock: AtomicBool::new(false),
+        rng: UnsafeCell::new(Box::new(rng)),
+    });
+    unsafe { mclBn_setRandFunc(Box::into_raw(src) as *mut c_void, Some(read_rand)) }
+}
+
+/// Switches libmcl back to its own CSPRNG.
+pub fn reset_rand_func() {
+    unsafe { mclBn_setRandFunc(core::ptr::null_mut(), None) }
+}
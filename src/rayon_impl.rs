@@ -0,0 +1,31 @@
+// multi-scalar multiplication split across the rayon thread pool, for
+// callers that already run one, instead of OpenMP inside libmcl
+use crate::{init_check, Error, Fr, Result, G1, G2};
+use rayon::prelude::*;
+
+// below this the per-chunk overhead outweighs the parallelism
+const MIN_CHUNK: usize = 256;
+
+macro_rules! par_msm_impl {
+    ($t:ty) => {
+        impl $t {
+            pub fn par_msm(x: &[$t], y: &[Fr]) -> Result<$t> {
+                init_check()?;
+                if x.len() != y.len() {
+                    return Err(Error::InvalidLength);
+                }
+                let chunk = x
+                    .len()
+                    .div_ceil(rayon::current_num_threads())
+                    .max(MIN_CHUNK);
+                x.par_chunks(chunk)
+                    .zip(y.par_chunks(chunk))
+                    .map(|(x, y)| <$t>::msm(x, y))
+                    .try_reduce(<$t>::zero, |a, b| Ok(a + b))
+            }
+        }
+    };
+}
+
+par_msm_impl![G1];
+par_msm_impl![G2];
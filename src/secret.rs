@@ -0,0 +1,62 @@
+use crate::{Fr, G1, G2, GT};
+use core::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A secret scalar that is wiped when dropped.
+///
+/// The value is only reachable through `expose`/`expose_mut`, so any copy
+/// taken out of the wrapper is visible at the call site.
+#[derive(Clone, Default)]
+pub struct SecretFr(Fr);
+
+impl SecretFr {
+    pub fn new(x: Fr) -> SecretFr {
+        SecretFr(x)
+    }
+    pub fn set_by_csprng(&mut self) {
+        self.0.set_by_csprng()
+    }
+    pub fn expose(&self) -> &Fr {
+        &self.0
+    }
+    pub fn expose_mut(&mut self) -> &mut Fr {
+        &mut self.0
+    }
+    // constant time scalar multiplication, e.g. a public key or a signature
+    pub fn mul_g1(&self, p: &G1) -> G1 {
+        p.mul_scalar_ct(&self.0)
+    }
+    pub fn mul_g2(&self, q: &G2) -> G2 {
+        q.mul_scalar_ct(&self.0)
+    }
+    // best effort only, see GT::pow_ladder
+    pub fn pow_gt(&self, x: &GT) -> GT {
+        x.pow_scalar_ladder(&self.0)
+    }
+}
+
+impl From<Fr> for SecretFr {
+    fn from(x: Fr) -> SecretFr {
+        SecretFr(x)
+    }
+}
+
+impl Drop for SecretFr {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretFr {}
+
+impl Zeroize for SecretFr {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretFr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretFr(<redacted>)")
+    }
+}
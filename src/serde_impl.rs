@@ -0,0 +1,99 @@
+use crate::{Error, Fp, Fp2, Fr, G1, G2, GT};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+fn to_hex(buf: &[u8]) -> String {
+    let mut s = String::with_capacity(buf.len() * 2);
+    for b in buf {
+        s.push(HEX[(b >> 4) as usize] as char);
+        s.push(HEX[(b & 15) as usize] as char);
+    }
+    s
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    fn nibble(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+    let s = s.as_bytes();
+    if s.len() & 1 != 0 {
+        return None;
+    }
+    s.chunks(2)
+        .map(|c| Some(nibble(c[0])? << 4 | nibble(c[1])?))
+        .collect()
+}
+
+struct BytesVisitor(&'static str);
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a serialized {}", self.0)
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+        from_hex(v).ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.into())
+    }
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            v.push(b);
+        }
+        Ok(v)
+    }
+}
+
+// hex string of the serialized form for human-readable formats, raw bytes otherwise
+macro_rules! serde_impl {
+    ($t:ty, $check:expr) => {
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let buf = self.serialize().map_err(serde::ser::Error::custom)?;
+                if serializer.is_human_readable() {
+                    serializer.serialize_str(&to_hex(&buf))
+                } else {
+                    serializer.serialize_bytes(&buf)
+                }
+            }
+        }
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$t, D::Error> {
+                let visitor = BytesVisitor(stringify!($t));
+                let buf = if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(visitor)?
+                } else {
+                    deserializer.deserialize_bytes(visitor)?
+                };
+                let mut v = <$t>::zero();
+                v.deserialize_raw(&buf).map_err(de::Error::custom)?;
+                let check: fn(&$t) -> Result<(), Error> = $check;
+                check(&v).map_err(de::Error::custom)?;
+                Ok(v)
+            }
+        }
+    };
+}
+
+serde_impl![Fr, |_| Ok(())];
+serde_impl![Fp, |_| Ok(())];
+serde_impl![Fp2, |_| Ok(())];
+serde_impl![G1, |v| v.check_order()];
+serde_impl![G2, |v| v.check_order()];
+serde_impl![GT, |v| v.check_order()];
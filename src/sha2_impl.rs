@@ -0,0 +1,71 @@
+// RFC 9380 hash_to_field with expand_message_xmd and SHA-256
+use crate::{get_fp_serialized_size, get_fr_serialized_size, init_check};
+use crate::{Error, Fp, Fr, Result};
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Result<Vec<u8>> {
+    let ell = len.div_ceil(32);
+    if ell > 255 || len > 0xffff {
+        return Err(Error::HashFailed);
+    }
+    let long_dst;
+    let dst = if dst.len() > 255 {
+        long_dst = Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize();
+        &long_dst[..]
+    } else {
+        dst
+    };
+    let with_dst = |h: Sha256| h.chain_update(dst).chain_update([dst.len() as u8]);
+    let b0 = with_dst(
+        Sha256::new()
+            .chain_update([0u8; 64])
+            .chain_update(msg)
+            .chain_update((len as u16).to_be_bytes())
+            .chain_update([0u8]),
+    )
+    .finalize();
+    let mut bi = with_dst(Sha256::new().chain_update(b0).chain_update([1u8])).finalize();
+    let mut out = Vec::with_capacity(ell * 32);
+    out.extend_from_slice(&bi);
+    for i in 2..=ell {
+        let mut x = [0u8; 32];
+        for (x, (a, b)) in x.iter_mut().zip(b0.iter().zip(bi.iter())) {
+            *x = a ^ b;
+        }
+        bi = with_dst(Sha256::new().chain_update(x).chain_update([i as u8])).finalize();
+        out.extend_from_slice(&bi);
+    }
+    out.truncate(len);
+    Ok(out)
+}
+
+// L = ceil((ceil(log2(p)) + 128) / 8) is the byte size plus 16 for every
+// supported curve
+macro_rules! hash_to_field_impl {
+    ($t:ty, $size_fn:ident) => {
+        impl $t {
+            pub fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<$t>> {
+                init_check()?;
+                let l = $size_fn() as usize + 16;
+                let bytes = expand_message_xmd(msg, dst, count * l)?;
+                bytes
+                    .chunks(l)
+                    .map(|c| {
+                        let mut le = c.to_vec();
+                        le.reverse();
+                        let mut v = <$t>::zero();
+                        v.set_little_endian_mod(&le)?;
+                        Ok(v)
+                    })
+                    .collect()
+            }
+        }
+    };
+}
+
+hash_to_field_impl![Fr, get_fr_serialized_size];
+hash_to_field_impl![Fp, get_fp_serialized_size];
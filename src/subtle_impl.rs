@@ -0,0 +1,131 @@
+// Constant-time helpers on top of the raw limbs.
+//
+// The Rust side never branches on secret data here. Field arithmetic used by
+// the point comparison is Montgomery multiplication in libmcl, which has no
+// data-dependent branches; inv, squareRoot and deserialize are called as-is and
+// only their success flag is turned into a `Choice`.
+use crate::{Fp, Fp2, Fr, G1, G2, GT};
+use core::mem::size_of;
+use core::slice;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+// every type is a repr(C) array of u64
+macro_rules! limbs_impl {
+    ($t:ty) => {
+        impl $t {
+            fn limbs(&self) -> &[u64] {
+                let p = self as *const $t as *const u64;
+                unsafe { slice::from_raw_parts(p, size_of::<$t>() / 8) }
+            }
+            fn limbs_mut(&mut self) -> &mut [u64] {
+                let p = self as *mut $t as *mut u64;
+                unsafe { slice::from_raw_parts_mut(p, size_of::<$t>() / 8) }
+            }
+            pub fn ct_deserialize(buf: &[u8]) -> CtOption<$t> {
+                let mut v = <$t>::zero();
+                let ok = v.deserialize(buf).is_ok();
+                CtOption::new(v, Choice::from(ok as u8))
+            }
+            fn select(a: &$t, b: &$t, choice: Choice) -> $t {
+                let mut v = <$t>::zero();
+                for ((v, a), b) in v.limbs_mut().iter_mut().zip(a.limbs()).zip(b.limbs()) {
+                    *v = u64::conditional_select(a, b, choice);
+                }
+                v
+            }
+        }
+    };
+}
+
+// ConditionallySelectable requires Copy
+macro_rules! select_impl {
+    ($t:ty) => {
+        impl ConditionallySelectable for $t {
+            fn conditional_select(a: &$t, b: &$t, choice: Choice) -> $t {
+                <$t>::select(a, b, choice)
+            }
+        }
+    };
+}
+
+// field elements are kept fully reduced, so equal values have equal limbs
+macro_rules! field_ct_impl {
+    ($t:ty) => {
+        limbs_impl![$t];
+        impl ConstantTimeEq for $t {
+            fn ct_eq(&self, other: &$t) -> Choice {
+                self.limbs().ct_eq(other.limbs())
+            }
+        }
+        impl $t {
+            pub fn ct_is_zero(&self) -> Choice {
+                self.limbs()
+                    .iter()
+                    .fold(Choice::from(1), |acc, x| acc & x.ct_eq(&0))
+            }
+            pub fn ct_inverse(&self) -> CtOption<$t> {
+                let mut v = <$t>::zero();
+                <$t>::inv(&mut v, self);
+                CtOption::new(v, !self.ct_is_zero())
+            }
+        }
+    };
+}
+
+macro_rules! sqrt_ct_impl {
+    ($t:ty) => {
+        impl $t {
+            pub fn ct_sqrt(&self) -> CtOption<$t> {
+                let mut v = <$t>::zero();
+                let ok = <$t>::square_root(&mut v, self).is_ok();
+                CtOption::new(v, Choice::from(ok as u8))
+            }
+        }
+    };
+}
+
+// compare jacobian coordinates (X1 Z2^2 == X2 Z1^2, Y1 Z2^3 == Y2 Z1^3)
+// without normalizing
+macro_rules! ec_ct_impl {
+    ($t:ty) => {
+        limbs_impl![$t];
+        impl ConstantTimeEq for $t {
+            fn ct_eq(&self, other: &$t) -> Choice {
+                let z1 = self.ct_is_zero();
+                let z2 = other.ct_is_zero();
+                let z1z1 = self.z.square();
+                let z2z2 = other.z.square();
+                let x_eq = (&self.x * &z2z2).ct_eq(&(&other.x * &z1z1));
+                let y_eq = (&self.y * &(&z2z2 * &other.z)).ct_eq(&(&other.y * &(&z1z1 * &self.z)));
+                (z1 & z2) | (!z1 & !z2 & x_eq & y_eq)
+            }
+        }
+        impl $t {
+            pub fn ct_is_zero(&self) -> Choice {
+                self.z.ct_is_zero()
+            }
+        }
+    };
+}
+
+field_ct_impl![Fr];
+field_ct_impl![Fp];
+field_ct_impl![Fp2];
+field_ct_impl![GT];
+sqrt_ct_impl![Fr];
+sqrt_ct_impl![Fp];
+sqrt_ct_impl![Fp2];
+ec_ct_impl![G1];
+ec_ct_impl![G2];
+select_impl![Fp];
+select_impl![Fp2];
+select_impl![GT];
+select_impl![G1];
+select_impl![G2];
+
+// Fr is not Copy, so it gets the same function without the trait
+impl Fr {
+    pub fn conditional_select(a: &Fr, b: &Fr, choice: Choice) -> Fr {
+        Fr::select(a, b, choice)
+    }
+}
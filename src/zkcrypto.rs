@@ -0,0 +1,810 @@
+// ff / group / pairing trait implementations.
+//
+// The traits need compile-time constants while mcl selects its curve at run
+// time, so every supported curve gets a module of thin wrappers around Fr,
+// G1, G2 and GT. Call the module's `init` first; the wrappers panic if mcl
+// was initialized with another curve. BN381 and BN_P256 have no module.
+//
+// Field constants are given in Montgomery form, as mcl stores them.
+
+// Gt and MillerLoopOutput are written additively
+#![allow(clippy::suspicious_arithmetic_impl)]
+
+use crate::{Fp, Fp2, GT, MCLBN_FP_UNIT_SIZE};
+
+const FP_ZERO: Fp = Fp {
+    d: [0; MCLBN_FP_UNIT_SIZE],
+};
+
+const fn gt_one(one: Fp) -> GT {
+    let mut d = [FP_ZERO; 12];
+    d[0] = one;
+    GT { d }
+}
+
+const fn fp2(c0: Fp, c1: Fp) -> Fp2 {
+    Fp2 { d: [c0, c1] }
+}
+
+// fixed size byte array usable as GroupEncoding::Repr
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Bytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Default for Bytes<N> {
+    fn default() -> Self {
+        Bytes([0u8; N])
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Bytes<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for Bytes<N> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+// `$lhs op $rhs` for every combination of owned and borrowed operands
+macro_rules! binop_impl {
+    ($lhs:ident, $rhs:ident, $out:ident, $op:ident, $op_fn:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl<'a, 'b> $op<&'b $rhs> for &'a $lhs {
+            type Output = $out;
+            fn $op_fn(self, other: &'b $rhs) -> $out {
+                // the assert_curve of the invoking module
+                assert_curve();
+                let ($a, $b) = (self, other);
+                $body
+            }
+        }
+        impl<'b> $op<&'b $rhs> for $lhs {
+            type Output = $out;
+            fn $op_fn(self, other: &'b $rhs) -> $out {
+                <&$lhs as $op<&$rhs>>::$op_fn(&self, other)
+            }
+        }
+        impl<'a> $op<$rhs> for &'a $lhs {
+            type Output = $out;
+            fn $op_fn(self, other: $rhs) -> $out {
+                <&$lhs as $op<&$rhs>>::$op_fn(self, &other)
+            }
+        }
+        impl $op<$rhs> for $lhs {
+            type Output = $out;
+            fn $op_fn(self, other: $rhs) -> $out {
+                <&$lhs as $op<&$rhs>>::$op_fn(&self, &other)
+            }
+        }
+    };
+}
+
+macro_rules! binop_assign_impl {
+    ($lhs:ident, $rhs:ident, $op:ident, $op_fn:ident, $op_assign:ident, $op_assign_fn:ident) => {
+        impl<'b> $op_assign<&'b $rhs> for $lhs {
+            fn $op_assign_fn(&mut self, other: &'b $rhs) {
+                *self = <&$lhs as $op<&$rhs>>::$op_fn(self, other);
+            }
+        }
+        impl $op_assign<$rhs> for $lhs {
+            fn $op_assign_fn(&mut self, other: $rhs) {
+                *self = <&$lhs as $op<&$rhs>>::$op_fn(self, &other);
+            }
+        }
+    };
+}
+
+macro_rules! fold_impl {
+    ($t:ident, $trait:ident, $fn:ident, $init:expr, $op_assign_fn:ident) => {
+        impl $trait for $t {
+            fn $fn<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold($init, |mut acc, x| {
+                    acc.$op_assign_fn(&x);
+                    acc
+                })
+            }
+        }
+        impl<'a> $trait<&'a $t> for $t {
+            fn $fn<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold($init, |mut acc, x| {
+                    acc.$op_assign_fn(x);
+                    acc
+                })
+            }
+        }
+    };
+}
+
+// projective and affine wrappers of G1 or G2
+macro_rules! curve_impl {
+    ($proj:ident, $affine:ident, $t:ident, $generator:expr, $size:expr, $pair:ident, |$p:ident, $q:ident| $pairing:expr) => {
+        #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+        pub struct $proj(pub $t);
+
+        // z is one, or zero for the identity
+        #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+        pub struct $affine(pub $t);
+
+        impl ConditionallySelectable for $proj {
+            fn conditional_select(a: &$proj, b: &$proj, choice: Choice) -> $proj {
+                $proj($t::conditional_select(&a.0, &b.0, choice))
+            }
+        }
+        impl ConditionallySelectable for $affine {
+            fn conditional_select(a: &$affine, b: &$affine, choice: Choice) -> $affine {
+                $affine($t::conditional_select(&a.0, &b.0, choice))
+            }
+        }
+        impl ConstantTimeEq for $proj {
+            fn ct_eq(&self, other: &$proj) -> Choice {
+                self.0.ct_eq(&other.0)
+            }
+        }
+        impl ConstantTimeEq for $affine {
+            fn ct_eq(&self, other: &$affine) -> Choice {
+                self.0.ct_eq(&other.0)
+            }
+        }
+
+        impl From<$affine> for $proj {
+            fn from(p: $affine) -> $proj {
+                $proj(p.0)
+            }
+        }
+        impl From<$proj> for $affine {
+            fn from(p: $proj) -> $affine {
+                assert_curve();
+                $affine(p.0.normalized())
+            }
+        }
+
+        impl Neg for $proj {
+            type Output = $proj;
+            fn neg(self) -> $proj {
+                assert_curve();
+                $proj(-self.0)
+            }
+        }
+        impl<'a> Neg for &'a $proj {
+            type Output = $proj;
+            fn neg(self) -> $proj {
+                assert_curve();
+                $proj(-self.0)
+            }
+        }
+        impl Neg for $affine {
+            type Output = $affine;
+            fn neg(self) -> $affine {
+                assert_curve();
+                $affine(-self.0)
+            }
+        }
+        binop_impl![$proj, $proj, $proj, Add, add, |a, b| $proj(a.0 + b.0)];
+        binop_impl![$proj, $proj, $proj, Sub, sub, |a, b| $proj(a.0 - b.0)];
+        binop_impl![$proj, $affine, $proj, Add, add, |a, b| $proj(a.0 + b.0)];
+        binop_impl![$proj, $affine, $proj, Sub, sub, |a, b| $proj(a.0 - b.0)];
+        binop_impl![$proj, Scalar, $proj, Mul, mul, |a, b| $proj(a.0 * b.fr())];
+        binop_impl![$affine, Scalar, $proj, Mul, mul, |a, b| $proj(a.0 * b.fr())];
+        binop_assign_impl![$proj, $proj, Add, add, AddAssign, add_assign];
+        binop_assign_impl![$proj, $proj, Sub, sub, SubAssign, sub_assign];
+        binop_assign_impl![$proj, $affine, Add, add, AddAssign, add_assign];
+        binop_assign_impl![$proj, $affine, Sub, sub, SubAssign, sub_assign];
+        binop_assign_impl![$proj, Scalar, Mul, mul, MulAssign, mul_assign];
+        fold_impl![$proj, Sum, sum, $proj::identity(), add_assign];
+
+        impl Group for $proj {
+            type Scalar = Scalar;
+            fn random(rng: impl RngCore) -> $proj {
+                $proj::generator() * Scalar::random(rng)
+            }
+            fn identity() -> $proj {
+                $proj($t::zero())
+            }
+            fn generator() -> $proj {
+                assert_curve();
+                $proj($generator)
+            }
+            fn is_identity(&self) -> Choice {
+                self.0.ct_is_zero()
+            }
+            fn double(&self) -> $proj {
+                assert_curve();
+                $proj(self.0.double())
+            }
+        }
+        impl Curve for $proj {
+            type AffineRepr = $affine;
+            fn to_affine(&self) -> $affine {
+                assert_curve();
+                $affine(self.0.normalized())
+            }
+        }
+        impl PrimeGroup for $proj {}
+        impl PrimeCurve for $proj {
+            type Affine = $affine;
+        }
+
+        impl PrimeCurveAffine for $affine {
+            type Scalar = Scalar;
+            type Curve = $proj;
+            fn identity() -> $affine {
+                $affine($t::zero())
+            }
+            fn generator() -> $affine {
+                assert_curve();
+                $affine($generator)
+            }
+            fn is_identity(&self) -> Choice {
+                self.0.ct_is_zero()
+            }
+            fn to_curve(&self) -> $proj {
+                $proj(self.0)
+            }
+        }
+
+        impl GroupEncoding for $proj {
+            type Repr = Bytes<$size>;
+            fn from_bytes(bytes: &Self::Repr) -> CtOption<$proj> {
+                $affine::from_bytes(bytes).map(|p| $proj(p.0))
+            }
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<$proj> {
+                $affine::from_bytes_unchecked(bytes).map(|p| $proj(p.0))
+            }
+            fn to_bytes(&self) -> Self::Repr {
+                $affine(self.0).to_bytes()
+            }
+        }
+        impl GroupEncoding for $affine {
+            type Repr = Bytes<$size>;
+            fn from_bytes(bytes: &Self::Repr) -> CtOption<$affine> {
+                $affine::from_bytes_unchecked(bytes).and_then(|p| {
+                    let ok = p.0.check_order().is_ok();
+                    CtOption::new(p, Choice::from(ok as u8))
+                })
+            }
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<$affine> {
+                assert_curve();
+                let mut v = $t::zero();
+                let ok = v.deserialize_unchecked(&bytes.0).is_ok();
+                CtOption::new($affine(v), Choice::from(ok as u8))
+            }
+            fn to_bytes(&self) -> Self::Repr {
+                assert_curve();
+                let mut r = Bytes::default();
+                self.0.serialize_into(&mut r.0).expect("serialize");
+                r
+            }
+        }
+
+        // x || y, all zero for the identity, see G1::to_uncompressed
+        impl UncompressedEncoding for $affine {
+            type Uncompressed = Bytes<{ $size * 2 }>;
+            fn from_uncompressed(bytes: &Self::Uncompressed) -> CtOption<$affine> {
+                $affine::from_uncompressed_unchecked(bytes).and_then(|p| {
+                    let ok = p.0.check_order().is_ok();
+                    CtOption::new(p, Choice::from(ok as u8))
+                })
+            }
+            fn from_uncompressed_unchecked(bytes: &Self::Uncompressed) -> CtOption<$affine> {
+                assert_curve();
+                let r = $t::from_uncompressed_unchecked(&bytes.0);
+                let ok = r.is_ok();
+                CtOption::new($affine(r.unwrap_or_default()), Choice::from(ok as u8))
+            }
+            fn to_uncompressed(&self) -> Self::Uncompressed {
+                assert_curve();
+                let mut r = Bytes::default();
+                self.0
+                    .to_uncompressed_into(&mut r.0)
+                    .expect("to_uncompressed_into");
+                r
+            }
+        }
+
+        impl PairingCurveAffine for $affine {
+            type Pair = $pair;
+            type PairingResult = Gt;
+            fn pairing_with(&self, other: &$pair) -> Gt {
+                let ($p, $q) = (self, other);
+                $pairing
+            }
+        }
+    };
+}
+
+macro_rules! zkcrypto_impl {
+    (
+        curve: $curve:expr,
+        engine: $engine:ident,
+        modulus: $modulus:expr,
+        num_bits: $num_bits:expr,
+        s: $s:expr,
+        one: $one:expr,
+        two_inv: $two_inv:expr,
+        generator: $gen:expr,
+        root_of_unity: $root:expr,
+        root_of_unity_inv: $root_inv:expr,
+        delta: $delta:expr,
+        fp_one: $fp_one:expr,
+        g1: ($g1x:expr, $g1y:expr),
+        g2: ($g2x0:expr, $g2x1:expr, $g2y0:expr, $g2y1:expr),
+        g1_size: $g1_size:expr,
+        g2_size: $g2_size:expr $(,)?
+    ) => {
+        use super::{fp2, gt_one, Bytes, FP_ZERO};
+        use crate::{CurveType, Fp, Fr, Result, G1, G2, GT};
+        use alloc::vec::Vec;
+        use core::fmt;
+        use core::iter::{Product, Sum};
+        use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+        use ff::{Field, PrimeField};
+        use group::prime::{PrimeCurve, PrimeCurveAffine, PrimeGroup};
+        use group::{Curve, Group, GroupEncoding, UncompressedEncoding};
+        use pairing::{Engine, MillerLoopResult, MultiMillerLoop, PairingCurveAffine};
+        use rand_core::RngCore;
+        use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+        const FP_ONE: Fp = Fp { d: $fp_one };
+        const GT_ONE: GT = gt_one(FP_ONE);
+        const G1_GENERATOR: G1 = G1 {
+            x: Fp { d: $g1x },
+            y: Fp { d: $g1y },
+            z: FP_ONE,
+        };
+        const G2_GENERATOR: G2 = G2 {
+            x: fp2(Fp { d: $g2x0 }, Fp { d: $g2x1 }),
+            y: fp2(Fp { d: $g2y0 }, Fp { d: $g2y1 }),
+            z: fp2(FP_ONE, FP_ZERO),
+        };
+
+        pub fn init() -> Result<()> {
+            crate::init($curve)
+        }
+
+        // the trait methods cannot fail, so a curve mismatch panics like
+        // assert_init; the constants would be garbage for another curve
+        fn assert_curve() {
+            if crate::get_curve_type() != Some($curve) {
+                panic!("mcl_rust: init({:?}) has not been called", $curve);
+            }
+        }
+
+        // Fr is never Copy since it may hold a secret key, but ff::Field
+        // needs Copy, so Scalar keeps the limbs and rebuilds an Fr to compute
+        #[derive(Default, Copy, Clone, PartialEq, Eq)]
+        pub struct Scalar([u64; 4]);
+
+        impl Scalar {
+            fn fr(&self) -> Fr {
+                Fr { d: self.0 }
+            }
+        }
+        impl From<Fr> for Scalar {
+            fn from(x: Fr) -> Scalar {
+                Scalar(x.d)
+            }
+        }
+        impl From<Scalar> for Fr {
+            fn from(x: Scalar) -> Fr {
+                x.fr()
+            }
+        }
+        impl fmt::Debug for Scalar {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple("Scalar").field(&self.fr()).finish()
+            }
+        }
+
+        impl From<u64> for Scalar {
+            fn from(v: u64) -> Scalar {
+                assert_curve();
+                let mut x = Fr::zero();
+                x.set_little_endian(&v.to_le_bytes())
+                    .expect("set_little_endian");
+                Scalar::from(x)
+            }
+        }
+        impl ConstantTimeEq for Scalar {
+            fn ct_eq(&self, other: &Scalar) -> Choice {
+                self.fr().ct_eq(&other.fr())
+            }
+        }
+        impl ConditionallySelectable for Scalar {
+            fn conditional_select(a: &Scalar, b: &Scalar, choice: Choice) -> Scalar {
+                Scalar::from(Fr::conditional_select(&a.fr(), &b.fr(), choice))
+            }
+        }
+        impl Neg for Scalar {
+            type Output = Scalar;
+            fn neg(self) -> Scalar {
+                assert_curve();
+                Scalar::from(-self.fr())
+            }
+        }
+        binop_impl![Scalar, Scalar, Scalar, Add, add, |a, b| Scalar::from(
+            a.fr() + b.fr()
+        )];
+        binop_impl![Scalar, Scalar, Scalar, Sub, sub, |a, b| Scalar::from(
+            a.fr() - b.fr()
+        )];
+        binop_impl![Scalar, Scalar, Scalar, Mul, mul, |a, b| Scalar::from(
+            a.fr() * b.fr()
+        )];
+        binop_assign_impl![Scalar, Scalar, Add, add, AddAssign, add_assign];
+        binop_assign_impl![Scalar, Scalar, Sub, sub, SubAssign, sub_assign];
+        binop_assign_impl![Scalar, Scalar, Mul, mul, MulAssign, mul_assign];
+        fold_impl![Scalar, Sum, sum, Scalar::ZERO, add_assign];
+        fold_impl![Scalar, Product, product, Scalar::ONE, mul_assign];
+
+        impl Field for Scalar {
+            const ZERO: Scalar = Scalar([0; 4]);
+            const ONE: Scalar = Scalar($one);
+            fn random(mut rng: impl RngCore) -> Scalar {
+                assert_curve();
+                let mut buf = [0u8; 64];
+                rng.fill_bytes(&mut buf);
+                let mut x = Fr::zero();
+                x.set_little_endian_mod(&buf)
+                    .expect("set_little_endian_mod");
+                Scalar::from(x)
+            }
+            fn square(&self) -> Scalar {
+                assert_curve();
+                Scalar::from(self.fr().square())
+            }
+            fn double(&self) -> Scalar {
+                assert_curve();
+                Scalar::from(self.fr() + self.fr())
+            }
+            fn invert(&self) -> CtOption<Scalar> {
+                assert_curve();
+                // CtOption::map needs Fr: ConditionallySelectable, so as ct_inverse
+                let x = self.fr();
+                let mut v = Fr::zero();
+                Fr::inv(&mut v, &x);
+                CtOption::new(Scalar::from(v), !x.ct_is_zero())
+            }
+            fn sqrt(&self) -> CtOption<Scalar> {
+                assert_curve();
+                let mut v = Fr::zero();
+                let ok = Fr::square_root(&mut v, &self.fr()).is_ok();
+                CtOption::new(Scalar::from(v), Choice::from(ok as u8))
+            }
+            fn sqrt_ratio(num: &Scalar, div: &Scalar) -> (Choice, Scalar) {
+                ff::helpers::sqrt_ratio_generic(num, div)
+            }
+        }
+
+        impl PrimeField for Scalar {
+            // little endian
+            type Repr = [u8; 32];
+            // rejects non-canonical encodings
+            fn from_repr(repr: [u8; 32]) -> CtOption<Scalar> {
+                assert_curve();
+                let mut x = Fr::zero();
+                let ok = x.set_little_endian(&repr).is_ok();
+                let v = Scalar::from(x);
+                let ok = ok && v.to_repr() == repr;
+                CtOption::new(v, Choice::from(ok as u8))
+            }
+            fn to_repr(&self) -> [u8; 32] {
+                assert_curve();
+                let mut buf = [0u8; 32];
+                self.fr()
+                    .write_little_endian(&mut buf)
+                    .expect("write_little_endian");
+                buf
+            }
+            fn is_odd(&self) -> Choice {
+                assert_curve();
+                Choice::from(self.fr().is_odd() as u8)
+            }
+            const MODULUS: &'static str = $modulus;
+            const NUM_BITS: u32 = $num_bits;
+            const CAPACITY: u32 = $num_bits - 1;
+            const TWO_INV: Scalar = Scalar($two_inv);
+            const MULTIPLICATIVE_GENERATOR: Scalar = Scalar($gen);
+            const S: u32 = $s;
+            const ROOT_OF_UNITY: Scalar = Scalar($root);
+            const ROOT_OF_UNITY_INV: Scalar = Scalar($root_inv);
+            const DELTA: Scalar = Scalar($delta);
+        }
+
+        curve_impl![
+            G1Projective,
+            G1Affine,
+            G1,
+            G1_GENERATOR,
+            $g1_size,
+            G2Affine,
+            |p, q| { $engine::pairing(p, q) }
+        ];
+        curve_impl![
+            G2Projective,
+            G2Affine,
+            G2,
+            G2_GENERATOR,
+            $g2_size,
+            G1Affine,
+            |q, p| { $engine::pairing(p, q) }
+        ];
+
+        // the target group written additively
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        pub struct Gt(pub GT);
+
+        impl Neg for Gt {
+            type Output = Gt;
+            fn neg(self) -> Gt {
+                assert_curve();
+                let mut v = GT::zero();
+                GT::inv(&mut v, &self.0);
+                Gt(v)
+            }
+        }
+        binop_impl![Gt, Gt, Gt, Add, add, |a, b| Gt(a.0 * b.0)];
+        binop_impl![Gt, Gt, Gt, Sub, sub, |a, b| Gt(a.0 / b.0)];
+        binop_impl![Gt, Scalar, Gt, Mul, mul, |a, b| Gt(a.0.pow_scalar(&b.fr()))];
+        binop_assign_impl![Gt, Gt, Add, add, AddAssign, add_assign];
+        binop_assign_impl![Gt, Gt, Sub, sub, SubAssign, sub_assign];
+        binop_assign_impl![Gt, Scalar, Mul, mul, MulAssign, mul_assign];
+        fold_impl![Gt, Sum, sum, Gt(GT_ONE), add_assign];
+
+        impl Group for Gt {
+            type Scalar = Scalar;
+            fn random(rng: impl RngCore) -> Gt {
+                Gt::generator() * Scalar::random(rng)
+            }
+            fn identity() -> Gt {
+                Gt(GT_ONE)
+            }
+            fn generator() -> Gt {
+                $engine::pairing(&G1Affine::generator(), &G2Affine::generator())
+            }
+            fn is_identity(&self) -> Choice {
+                self.0.ct_eq(&GT_ONE)
+            }
+            fn double(&self) -> Gt {
+                assert_curve();
+                Gt(self.0.square())
+            }
+        }
+
+        // product of Miller loops, before the final exponentiation
+        #[derive(Debug, Copy, Clone)]
+        pub struct MillerLoopOutput(pub GT);
+
+        impl Default for MillerLoopOutput {
+            fn default() -> MillerLoopOutput {
+                MillerLoopOutput(GT_ONE)
+            }
+        }
+        binop_impl![
+            MillerLoopOutput,
+            MillerLoopOutput,
+            MillerLoopOutput,
+            Add,
+            add,
+            |a, b| MillerLoopOutput(a.0 * b.0)
+        ];
+        binop_assign_impl![
+            MillerLoopOutput,
+            MillerLoopOutput,
+            Add,
+            add,
+            AddAssign,
+            add_assign
+        ];
+
+        impl MillerLoopResult for MillerLoopOutput {
+            type Gt = Gt;
+            fn final_exponentiation(&self) -> Gt {
+                assert_curve();
+                let mut v = GT::zero();
+                crate::final_exp(&mut v, &self.0);
+                Gt(v)
+            }
+        }
+
+        #[derive(Debug, Copy, Clone)]
+        pub struct G2Prepared(pub G2Affine);
+
+        impl From<G2Affine> for G2Prepared {
+            fn from(q: G2Affine) -> G2Prepared {
+                G2Prepared(q)
+            }
+        }
+
+        #[derive(Debug, Copy, Clone)]
+        pub struct $engine;
+
+        impl Engine for $engine {
+            type Fr = Scalar;
+            type G1 = G1Projective;
+            type G1Affine = G1Affine;
+            type G2 = G2Projective;
+            type G2Affine = G2Affine;
+            type Gt = Gt;
+            fn pairing(p: &G1Affine, q: &G2Affine) -> Gt {
+                assert_curve();
+                let mut v = GT::zero();
+                crate::pairing(&mut v, &p.0, &q.0);
+                Gt(v)
+            }
+        }
+
+        impl MultiMillerLoop for $engine {
+            type G2Prepared = G2Prepared;
+            type Result = MillerLoopOutput;
+            fn multi_miller_loop(terms: &[(&G1Affine, &G2Prepared)]) -> MillerLoopOutput {
+                assert_curve();
+                let (ps, qs): (Vec<G1>, Vec<G2>) =
+                    terms.iter().map(|(p, q)| (p.0, (q.0).0)).unzip();
+                let mut e = GT::zero();
+                crate::multi_miller_loop(&mut e, &ps, &qs).expect("multi_miller_loop");
+                MillerLoopOutput(e)
+            }
+        }
+    };
+}
+
+pub mod bls12_381 {
+    zkcrypto_impl! {
+        curve: CurveType::BLS12_381,
+        engine: Bls12,
+        modulus: "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+        num_bits: 255,
+        s: 32,
+        one: [0x00000001fffffffe, 0x5884b7fa00034802, 0x998c4fefecbc4ff5, 0x1824b159acc5056f],
+        two_inv: [0x00000000ffffffff, 0xac425bfd0001a401, 0xccc627f7f65e27fa, 0x0c1258acd66282b7],
+        generator: [0x0000000efffffff1, 0x17e363d300189c0f, 0xff9c57876f8457b0, 0x351332208fc5a8c4],
+        root_of_unity: [0xb9b58d8c5f0e466a, 0x5b1b4c801819d7ec, 0x0af53ae352a31e64, 0x5bf3adda19e9b27b],
+        root_of_unity_inv: [0x4256481adcf3219a, 0x45f37b7f96b6cad3, 0xf9c3f1d75f7a3b27, 0x2d2fc049658afd43],
+        delta: [0x70e310d3d146f96a, 0x4b64c08919e299e6, 0x51e114186a8b970d, 0x6185d06627c067cb],
+        fp_one: [
+            0x760900000002fffd, 0xebf4000bc40c0002, 0x5f48985753c758ba,
+            0x77ce585370525745, 0x5c071a97a256ec6d, 0x15f65ec3fa80e493,
+        ],
+        g1: (
+            [
+                0x5cb38790fd530c16, 0x7817fc679976fff5, 0x154f95c7143ba1c1,
+                0xf0ae6acdf3d0e747, 0xedce6ecc21dbf440, 0x120177419e0bfb75,
+            ],
+            [
+                0xbaac93d50ce72271, 0x8c22631a7918fd8e, 0xdd595f13570725ce,
+                0x51ac582950405194, 0x0e1c8c3fad0059c0, 0x0bbc3efc5008a26a,
+            ]
+        ),
+        g2: (
+            [
+                0xf5f28fa202940a10, 0xb3f5fb2687b4961a, 0xa1a893b53e2ae580,
+                0x9894999d1a3caee9, 0x6f67b7631863366b, 0x058191924350bcd7,
+            ],
+            [
+                0xa5a9c0759e23f606, 0xaaa0c59dbccd60c3, 0x3bb17e18e2867806,
+                0x1b1ab6cc8541b367, 0xc2b6ed0ef2158547, 0x11922a097360edf3,
+            ],
+            [
+                0x4c730af860494c4a, 0x597cfa1f5e369c5a, 0xe7e6856caa0a635a,
+                0xbbefb5e96e0d495f, 0x07d3a975f0ef25a2, 0x0083fd8e7e80dae5,
+            ],
+            [
+                0xadc0fc92df64b05d, 0x18aa270a2b1461dc, 0x86adac6a3be4eba0,
+                0x79495c4ec93da33a, 0xe7175850a43ccaed, 0x0b2bc2a163de1bf2,
+            ]
+        ),
+        g1_size: 48,
+        g2_size: 96,
+    }
+}
+
+pub mod bls12_377 {
+    zkcrypto_impl! {
+        curve: CurveType::BLS12_377,
+        engine: Bls12_377,
+        modulus: "0x12ab655e9a2ca55660b44d1e5c37b00159aa76fed00000010a11800000000001",
+        num_bits: 253,
+        s: 47,
+        one: [0x7d1c7ffffffffff3, 0x7257f50f6ffffff2, 0x16d81575512c0fee, 0x0d4bda322bbb9a9d],
+        two_inv: [0xc396fffffffffffa, 0xe60136071ffffff9, 0xbbc63149d6b1dff7, 0x0ffb9fc862f41ff9],
+        generator: [0x296c7ffffffffed3, 0x929216656ffffec7, 0x4c01534d92860e69, 0x0c79cfc4b9819970],
+        root_of_unity: [0xaf80da4dda3ad648, 0x5e223adbfc381dac, 0x03ba0666b2f92525, 0x0f906c5b3befb0ce],
+        root_of_unity_inv: [0x0d248e974767f5bd, 0xfa72032b32f67f4c, 0x7ec7e591ee4ee58f, 0x1227d66f8e126f27],
+        delta: [0xc81be8416710aa44, 0xaaaff6305effee12, 0x5af037f7d8551509, 0x0f34eacc4d456e15],
+        fp_one: [
+            0x02cdffffffffff68, 0x51409f837fffffb1, 0x9f7db3a98a7d3ff2,
+            0x7b4e97b76e7c6305, 0x4cf495bf803c84e8, 0x008d6661e2fdf49a,
+        ],
+        g1: (
+            [
+                0x260f33b9772451f4, 0xc54dd773169d5658, 0x5c1551c469a510dd,
+                0x761662e4425e1698, 0xc97d78cc6f065272, 0x00a41206b361fd4d,
+            ],
+            [
+                0x8193961fb8cb81f3, 0x00638d4c5f44adb8, 0xfafaf3dad4daf54a,
+                0xc27849e2d655cd18, 0x2ec3ddb401d52814, 0x007da93326303c71,
+            ]
+        ),
+        g2: (
+            [
+                0x68904082f268725b, 0x668f2ea74f45328b, 0xebca7a65802be84f,
+                0x1e1850f4c1ada3e6, 0x830dc22d588ef1e9, 0x01862a81767c0982,
+            ],
+            [
+                0x5f02a915c91c7f39, 0xf8c553ba388da2a7, 0xd51a416dbd198850,
+                0xe943c6f38ae3073a, 0xffe24aa8259a4981, 0x011853391e73dfdd,
+            ],
+            [
+                0xd5b19b897881430f, 0x05be9118a5b371ed, 0x6063f91f86c131ee,
+                0x3244a61be8f4ec19, 0xa02e425b9f9a3a12, 0x018af8c04f3360d2,
+            ],
+            [
+                0x57601ac71a5b96f5, 0xe99acc1714f2440e, 0x2339612f10118ea9,
+                0x8321e68a3b1cd722, 0x2b543b050cc74917, 0x00590182b396c112,
+            ]
+        ),
+        g1_size: 48,
+        g2_size: 96,
+    }
+}
+// alt_bn128 as used by Ethereum
+pub mod snark {
+    zkcrypto_impl! {
+        curve: CurveType::SNARK,
+        engine: Snark,
+        modulus: "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+        num_bits: 254,
+        s: 28,
+        one: [0xac96341c4ffffffb, 0x36fc76959f60cd29, 0x666ea36f7879462e, 0x0e0a77c19a07df2f],
+        two_inv: [0x783c14d81ffffffe, 0xaf982f6f0c8d1edd, 0x8f5f7492fcfd4f45, 0x1f37631a3d9cbfac],
+        generator: [0x1b0d0ef99fffffe6, 0xeaba68a3a32a913f, 0x47d8eb76d8dd0689, 0x15d0085520f5bbc3],
+        root_of_unity: [0x636e735580d13d9c, 0xa22bf3742445ffd6, 0x56452ac01eb203d8, 0x1860ef942963f9e7],
+        root_of_unity_inv: [0x89bcc016584bb683, 0xe8d9887f0164a50c, 0x755e95cb795eda3d, 0x0f572b871323b130],
+        delta: [0xb1132acfdd0ede60, 0xbb4b2bd501254442, 0xb3559919ba247a31, 0x1a7adfe2c8b1068c],
+        fp_one: [0xd35d438dc58f0d9d, 0x0a78eb28f5c70b3d, 0x666ea36f7879462c, 0x0e0a77c19a07df2f, 0, 0],
+        g1: (
+            [0xd35d438dc58f0d9d, 0x0a78eb28f5c70b3d, 0x666ea36f7879462c, 0x0e0a77c19a07df2f, 0, 0],
+            [0xa6ba871b8b1e1b3a, 0x14f1d651eb8e167b, 0xccdd46def0f28c58, 0x1c14ef83340fbe5e, 0, 0]
+        ),
+        g2: (
+            [0x8e83b5d102bc2026, 0xdceb1935497b0172, 0xfbb8264797811adf, 0x19573841af96503b, 0, 0],
+            [0xafb4737da84c6140, 0x6043dd5a5802d8c4, 0x09e950fc52a02f86, 0x14fef0833aea7b6b, 0, 0],
+            [0x619dfa9d886be9f6, 0xfe7fd297f59e9b78, 0xff9e1a62231b7dfe, 0x28fd7eebae9e4206, 0, 0],
+            [0x64095b56c71856ee, 0xdc57f922327d3cbb, 0x55f935be33351076, 0x0da4a0e693fd6482, 0, 0]
+        ),
+        g1_size: 32,
+        g2_size: 64,
+    }
+}
+
+// mcl's own BN254, not alt_bn128
+pub mod bn254 {
+    zkcrypto_impl! {
+        curve: CurveType::BN254,
+        engine: Bn254,
+        modulus: "0x2523648240000001ba344d8000000007ff9f800000000010a10000000000000d",
+        num_bits: 254,
+        s: 2,
+        one: [0x39ffffffffffffb2, 0x0242ffffffffff9c, 0xa2c62effffffffd0, 0x212ba4f27ffffff5],
+        two_inv: [0x1cffffffffffffd9, 0x01217fffffffffce, 0xd163177fffffffe8, 0x1095d2793ffffffa],
+        generator: [0xd2ffffffffffff57, 0x04e67fffffffff27, 0x8b58107fffffff98, 0x1d33e562bfffffe9],
+        root_of_unity: [0xadb6ab563d2b70cf, 0xad5262c01f2d7d3d, 0x73742c8fe03f82bd, 0x125cec68c0000034],
+        root_of_unity_inv: [0xf34954a9c2d48f3e, 0x524d1d3fe0d282d2, 0x46c020f01fc07d4a, 0x12c678197fffffcd],
+        delta: [0xd1fffffffffffa6a, 0x2976fffffffff8da, 0xfd86b2fffffffc90, 0x0acad0087fffff41],
+        fp_one: [0x15ffffffffffff8e, 0xb939ffffffffff8a, 0xa2c62effffffffcd, 0x212ba4f27ffffff5, 0, 0],
+        g1: (
+            [0x9100000000000085, 0xa7e7000000000089, 0x176e1e800000003a, 0x03f7bf8fc000000c, 0, 0],
+            [0x15ffffffffffff8e, 0xb939ffffffffff8a, 0xa2c62effffffffcd, 0x212ba4f27ffffff5, 0, 0]
+        ),
+        g2: (
+            [0xaf45aaaf63659e66, 0xbb92f0233d01bc7c, 0xef7ab941dcd46337, 0x173f71de61df5b55, 0, 0],
+            [0x389bbb43d0d2f4e7, 0x413d1e26bc087b6a, 0x1fce93827a42e357, 0x10c76bd06630c4e9, 0, 0],
+            [0x25d0b13674482015, 0x5a0fcf130c7d1012, 0x9215e25e884b2cc7, 0x182c7a581c2d120d, 0, 0],
+            [0xfbb0d5e1fcb5d7aa, 0x6ce6262aa3a25124, 0x1e0d27e0927f8598, 0x1598cffe781d1a20, 0, 0]
+        ),
+        g1_size: 32,
+        g2_size: 64,
+    }
+}
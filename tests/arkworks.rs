@@ -0,0 +1,71 @@
+#![cfg(feature = "arkworks")]
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_ec::{AffineRepr, CurveGroup};
+use mcl_rust::*;
+use std::convert::TryFrom;
+
+macro_rules! ark_test {
+    ($ark:ident, $engine:ident) => {{
+        let x = Fr::from_int(123456789);
+        let ax = $ark::Fr::try_from(&x).unwrap();
+        assert_eq!(ax, $ark::Fr::from(123456789u64));
+        assert_eq!(Fr::try_from(ax).unwrap(), x);
+        let mut y = Fp::zero();
+        y.set_by_csprng();
+        assert_eq!(Fp::try_from($ark::Fq::try_from(y).unwrap()).unwrap(), y);
+
+        // generators agree
+        let g1 = $ark::G1Affine::generator();
+        let g2 = $ark::G2Affine::generator();
+        let P = G1::try_from(g1).unwrap();
+        let Q = G2::try_from(g2).unwrap();
+        assert!(P.is_valid());
+        assert!(Q.is_valid());
+        assert_eq!($ark::G1Affine::try_from(P).unwrap(), g1);
+        assert_eq!($ark::G2Affine::try_from(Q).unwrap(), g2);
+
+        // points from mcl land on the arkworks curve
+        let mut P2 = G1::zero();
+        let mut Q2 = G2::zero();
+        P2.set_hash_of(b"abc").unwrap();
+        Q2.set_hash_of(b"abc").unwrap();
+        let p2 = $ark::G1Affine::try_from(P2).unwrap();
+        let q2 = $ark::G2Affine::try_from(Q2).unwrap();
+        assert!(p2.is_on_curve() && p2.is_in_correct_subgroup_assuming_on_curve());
+        assert!(q2.is_on_curve() && q2.is_in_correct_subgroup_assuming_on_curve());
+        assert_eq!(G1::try_from(p2).unwrap(), P2);
+        assert_eq!(G2::try_from(q2).unwrap(), Q2);
+        assert_eq!(
+            $ark::G1Affine::try_from(P2 * x.clone()).unwrap(),
+            (p2 * ax).into_affine()
+        );
+        assert_eq!(G2::try_from(q2 * ax).unwrap(), Q2 * x);
+        let id = $ark::G1Affine::try_from(G1::zero()).unwrap();
+        assert!(id.is_zero());
+        assert!(G1::try_from(id).unwrap().is_zero());
+
+        // pairings agree
+        let mut e = GT::zero();
+        pairing(&mut e, &P2, &Q2);
+        let ae = $ark::$engine::pairing(p2, q2);
+        assert_eq!(PairingOutput::<$ark::$engine>::try_from(e).unwrap(), ae);
+        assert_eq!(GT::try_from(ae).unwrap(), e);
+        pairing(&mut e, &P, &Q);
+        assert_eq!(GT::try_from($ark::$engine::pairing(g1, g2)).unwrap(), e);
+    }};
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_arkworks() {
+    init(CurveType::SNARK).unwrap();
+    ark_test!(ark_bn254, Bn254);
+    assert_eq!(
+        ark_bls12_381::Fr::try_from(Fr::from_int(1)),
+        Err(Error::CurveMismatch)
+    );
+    init(CurveType::BLS12_381).unwrap();
+    ark_test!(ark_bls12_381, Bls12_381);
+    init(CurveType::BLS12_377).unwrap();
+    ark_test!(ark_bls12_377, Bls12_377);
+}
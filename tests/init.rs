@@ -0,0 +1,47 @@
+use mcl_rust::*;
+
+// kept in its own test binary so that no other test has called init yet
+#[test]
+#[allow(non_snake_case)]
+fn test_not_initialized() {
+    assert!(!is_initialized());
+    assert_eq!(get_curve_type(), None);
+    assert_eq!(Mcl::get(), Err(Error::NotInitialized));
+
+    let mut x = Fr::zero();
+    assert_eq!(x.set_str("1", 10), Err(Error::NotInitialized));
+    assert_eq!(x.serialize(), Err(Error::NotInitialized));
+    assert_eq!(x.deserialize(&[1]), Err(Error::NotInitialized));
+    let mut P = G1::zero();
+    assert_eq!(P.set_hash_of(b"abc"), Err(Error::NotInitialized));
+    assert_eq!(get_curve_order(), Err(Error::NotInitialized));
+    assert!(std::panic::catch_unwind(|| Fr::from_int(1)).is_err());
+    assert_eq!(
+        G1::from_affine(&Fp::zero(), &Fp::zero()),
+        Err(Error::NotInitialized)
+    );
+
+    let mcl = Mcl::init(CurveType::BLS12_381).unwrap();
+    assert_eq!(mcl.curve_type(), CurveType::BLS12_381);
+    assert_eq!(Mcl::get(), Ok(mcl));
+    assert_eq!(get_curve_type(), Some(CurveType::BLS12_381));
+    assert!(x.set_str("1", 10).is_ok());
+    assert!(x.is_one());
+    assert!(P.set_hash_of(b"abc").is_ok());
+
+    // the table of a G2Prepared is only valid for the curve it was made with
+    let mut Q = G2::zero();
+    Q.set_hash_of(b"abc").unwrap();
+    let Qp = G2Prepared::new(&Q).unwrap();
+    let mut e = GT::zero();
+    assert!(precomputed_miller_loop(&mut e, &P, &Qp).is_ok());
+    Mcl::init(CurveType::BN254).unwrap();
+    assert_eq!(
+        precomputed_miller_loop(&mut e, &P, &Qp),
+        Err(Error::CurveMismatch)
+    );
+    assert_eq!(
+        precomputed_miller_loop2(&mut e, &P, &Qp, &P, &Qp),
+        Err(Error::CurveMismatch)
+    );
+}
@@ -1,4 +1,7 @@
 use mcl_rust::*;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::convert::{TryFrom, TryInto};
 use std::mem;
 
 macro_rules! field_test {
@@ -15,10 +18,12 @@ macro_rules! field_test {
         assert_eq!(x, y);
         y.set_int(2);
         assert!(x != y);
-        x.set_str("65535", 10);
-        y.set_str("ffff", 16);
+        x.set_str("65535", 10).unwrap();
+        y.set_str("ffff", 16).unwrap();
         assert!(x.is_valid());
         assert_eq!(x, y);
+        assert_eq!(x.set_str("xyz", 10), Err(Error::InvalidEncoding));
+        assert_eq!(<$t>::from_str_radix("", 16), Err(Error::InvalidEncoding));
         x.set_int(123);
         assert!(x.is_odd());
         x.set_int(124);
@@ -28,15 +33,27 @@ macro_rules! field_test {
         assert!(x.is_negative());
         x.set_int(5);
         y.set_int(2);
-        assert_eq!(x.cmp(&y), 1);
-        assert_eq!(x.cmp(&x), 0);
-        assert_eq!(y.cmp(&x), -1);
+        assert_eq!(x.cmp(&y), Ordering::Greater);
+        assert_eq!(x.cmp(&x), Ordering::Equal);
+        assert_eq!(y.cmp(&x), Ordering::Less);
         y.set_int(-2); // unsigned large number
-        assert_eq!(x.cmp(&y), -1);
-        assert_eq!(y.cmp(&x), 1);
+        assert_eq!(x.cmp(&y), Ordering::Less);
+        assert_eq!(y.cmp(&x), Ordering::Greater);
+        assert!(x < y);
+        let mut v = vec![y.clone(), x.clone(), <$t>::from_int(1)];
+        v.sort();
+        assert_eq!(v, [<$t>::from_int(1), x.clone(), y.clone()]);
 
-        let mut z = unsafe { <$t>::uninit() };
-        let mut w = unsafe { <$t>::uninit() };
+        // Display, LowerHex and FromStr
+        x.set_int(255);
+        assert_eq!(format!("{}", x), "255");
+        assert_eq!(format!("{:x}", x), "ff");
+        assert_eq!("255".parse::<$t>(), Ok(x.clone()));
+        assert_eq!("0xff".parse::<$t>(), Ok(x.clone()));
+        assert_eq!("zz".parse::<$t>(), Err(Error::InvalidEncoding));
+
+        let mut z = <$t>::zero();
+        let mut w = <$t>::zero();
 
         let a = 256;
         let b = 8;
@@ -74,17 +91,57 @@ macro_rules! field_test {
         z /= &y;
         assert_eq!(z, w);
 
-        assert!(x.set_little_endian_mod(&[1, 2, 3, 4, 5]));
-        assert_eq!(x.get_str(16), "504030201");
+        assert!(x.set_little_endian_mod(&[1, 2, 3, 4, 5]).is_ok());
+        assert_eq!(x.get_str(16).unwrap(), "504030201");
         <$t>::sqr(&mut y, &x);
         <$t>::mul(&mut z, &x, &x);
         assert_eq!(y, z);
 
-        assert!(<$t>::square_root(&mut w, &y));
+        assert!(<$t>::square_root(&mut w, &y).is_ok());
         if w != x {
             <$t>::neg(&mut z, &w);
             assert_eq!(x, z);
         }
+        // a quadratic non-residue times a square is a non-residue
+        let mut nqr = <$t>::from_int(-1);
+        while <$t>::square_root(&mut w, &nqr).is_ok() {
+            nqr += &<$t>::from_int(1);
+        }
+        <$t>::mul(&mut z, &nqr, &y);
+        assert_eq!(<$t>::square_root(&mut w, &z), Err(Error::NoSquareRoot));
+        assert_eq!(z.sqrt(), Err(Error::NoSquareRoot));
+        w = y.sqrt().unwrap();
+        assert!(w == x || w == -&x);
+
+        // returning style
+        assert_eq!(x.square(), y);
+        assert_eq!(x.inverse().unwrap(), &<$t>::from_int(1) / &x);
+        assert_eq!(<$t>::zero().inverse(), None);
+        <$t>::neg(&mut z, &x);
+        assert_eq!(-&x, z);
+        assert_eq!(-x.clone(), z);
+
+        // by-value and mixed operators
+        let (a, b) = (<$t>::from_int(7), <$t>::from_int(3));
+        assert_eq!(a.clone() + b.clone(), <$t>::from_int(10));
+        assert_eq!(&a + b.clone(), <$t>::from_int(10));
+        assert_eq!(a.clone() - &b, <$t>::from_int(4));
+        assert_eq!(a.clone() * b.clone(), <$t>::from_int(21));
+        assert_eq!((a.clone() * &b) / b.clone(), a);
+        z = a.clone();
+        z += b.clone();
+        z -= a.clone();
+        z *= b.clone();
+        z /= b.clone();
+        assert_eq!(z, b);
+
+        let v: Vec<$t> = (1..=5).map(<$t>::from_int).collect();
+        assert_eq!(v.iter().sum::<$t>(), <$t>::from_int(15));
+        assert_eq!(v.clone().into_iter().sum::<$t>(), <$t>::from_int(15));
+        assert_eq!(v.iter().product::<$t>(), <$t>::from_int(120));
+        assert_eq!(v.into_iter().product::<$t>(), <$t>::from_int(120));
+        assert!(Vec::<$t>::new().iter().sum::<$t>().is_zero());
+        assert!(Vec::<$t>::new().iter().product::<$t>().is_one());
     }};
 }
 
@@ -96,13 +153,13 @@ macro_rules! ec_test {
         assert!(P1.is_zero());
         assert_ne!(P1, $P);
         <$t>::neg(&mut P1, &$P);
-        let mut x: $f = unsafe { <$f>::uninit() };
+        let mut x: $f = <$f>::zero();
         <$f>::neg(&mut x, &P1.y);
         assert_eq!(&x, &$P.y);
 
         <$t>::dbl(&mut P1, &$P);
-        let mut P2: $t = unsafe { <$t>::uninit() };
-        let mut P3: $t = unsafe { <$t>::uninit() };
+        let mut P2: $t = <$t>::zero();
+        let mut P3: $t = <$t>::zero();
         <$t>::add(&mut P2, &$P, &$P);
         assert_eq!(P2, P1);
         <$t>::add(&mut P3, &P2, &$P);
@@ -123,9 +180,46 @@ macro_rules! ec_test {
 
         P2 -= &$P;
         assert_eq!(P2, P1);
-        P1.set_hash_of(b"abcd");
+        P1.set_hash_of(b"abcd").unwrap();
         assert!(P1.is_valid());
 
+        // returning style
+        <$t>::dbl(&mut P2, &P1);
+        assert_eq!(P1.double(), P2);
+        assert_eq!(P1.mul_scalar(&Fr::from_int(2)), P2);
+        <$t>::neg(&mut P2, &P1);
+        assert_eq!(-&P1, P2);
+        assert_eq!(-P1.clone(), P2);
+        let y3 = Fr::from_int(3);
+        assert_eq!(&$P * &y3, P3);
+        assert_eq!($P.mul_scalar_ct(&y3), P3);
+        let mut r = Fr::zero();
+        r.set_by_csprng();
+        let mut R = <$t>::zero();
+        <$t>::mul_ct(&mut R, &P1, &r);
+        assert_eq!(R, P1.mul_scalar(&r));
+        assert!($P.mul_scalar_ct(&Fr::zero()).is_zero());
+        assert_eq!($P.clone() * y3.clone(), P3);
+        P2 = $P.clone();
+        P2 *= &y3;
+        assert_eq!(P2, P3);
+        assert_eq!($P.clone() + $P.clone() + &$P - P3.clone(), <$t>::zero());
+        <$t>::normalize(&mut P2, &P3);
+        assert_eq!(P3.normalized().get_str(16), P2.get_str(16));
+
+        // points in different jacobian coordinates hash alike
+        let mut set = HashSet::new();
+        set.insert(P3.clone());
+        assert!(set.contains(&P2));
+        assert!(!set.contains(&P1));
+        assert_eq!(format!("{}", P2), P2.get_str(10).unwrap());
+        assert_eq!(format!("{:x}", P2), P2.get_str(16).unwrap());
+        assert_eq!(P2.to_string().parse::<$t>(), Ok(P2.clone()));
+        assert_eq!(
+            format!("{:?}", P2),
+            format!("{}({})", stringify!($t), P2.get_str(16).unwrap())
+        );
+
         // mul_vec test
         let tbl = [0, 1, 2, 3, 4, 15, 16, 50, 300];
         for n in tbl {
@@ -137,34 +231,77 @@ macro_rules! ec_test {
             for i in 0..n {
                 ys[i].set_by_csprng();
                 <$t>::mul(&mut xs[i], &$P, &ys[i]);
-                let mut yy = unsafe { Fr::uninit() };
+                let mut yy = Fr::zero();
                 <Fr>::sqr(&mut yy, &ys[i]);
                 y += &yy;
             }
-            let mut g1 = unsafe { <$t>::uninit() };
-            let mut g2 = unsafe { <$t>::uninit() };
-            <$t>::mul_vec(&mut g1, &xs, &ys);
+            let mut g1 = <$t>::zero();
+            let mut g2 = <$t>::zero();
+            <$t>::mul_vec(&mut g1, &xs, &ys).unwrap();
             <$t>::mul(&mut g2, &$P, &y);
-            assert_eq!(g1.get_str(16), g2.get_str(16));
+            assert_eq!(g1.get_str(16).unwrap(), g2.get_str(16).unwrap());
+            assert_eq!(<$t>::msm(&xs, &ys), Ok(g2));
+            assert_eq!(<$t>::msm_mt(&xs, &ys, 0), Ok(g2));
+            assert_eq!(<$t>::msm_mt(&xs, &ys, 2), Ok(g2));
+            #[cfg(feature = "rayon")]
+            assert_eq!(<$t>::par_msm(&xs, &ys), Ok(g2));
+            let sum: $t = xs.iter().sum();
+            assert_eq!(Ok(sum), <$t>::msm(&xs, &vec![Fr::one(); n]));
+            assert_eq!(xs.into_iter().sum::<$t>(), sum);
         }
+        let xs = [$P];
+        assert_eq!(<$t>::msm(&xs, &[]), Err(Error::InvalidLength));
+        assert_eq!(<$t>::msm_mt(&xs, &[], 0), Err(Error::InvalidLength));
+        #[cfg(feature = "rayon")]
+        assert_eq!(<$t>::par_msm(&xs, &[]), Err(Error::InvalidLength));
     };
 }
 
 macro_rules! serialize_test {
     ($t:ty, $x:expr) => {
-        let buf = $x.serialize();
-        let mut y: $t = unsafe { <$t>::uninit() };
-        assert!(y.deserialize(&buf));
+        let buf = $x.serialize().unwrap();
+        let mut y: $t = <$t>::zero();
+        assert!(y.deserialize(&buf).is_ok());
         assert_eq!($x, y);
+        assert_eq!(y.deserialize(&[]), Err(Error::InvalidEncoding));
+        let mut long = buf.clone();
+        long.push(0);
+        assert_eq!(y.deserialize(&long), Err(Error::InvalidEncoding));
+        let mut out = [0u8; <$t>::MAX_SERIALIZED_SIZE];
+        let n = $x.serialize_into(&mut out).unwrap();
+        assert_eq!(&out[..n], &buf[..]);
+        assert_eq!(
+            $x.serialize_into(&mut out[..n - 1]),
+            Err(Error::BufferTooSmall)
+        );
+    };
+}
+
+#[cfg(feature = "serde")]
+macro_rules! serde_test {
+    ($t:ty, $x:expr) => {
+        let json = serde_json::to_string(&$x).unwrap();
+        let hex: String = $x
+            .serialize()
+            .unwrap()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        assert_eq!(json, format!("\"{}\"", hex));
+        assert_eq!(serde_json::from_str::<$t>(&json).unwrap(), $x);
+        let bin = bincode::serialize(&$x).unwrap();
+        assert_eq!(bincode::deserialize::<$t>(&bin).unwrap(), $x);
+        assert!(serde_json::from_str::<$t>("\"zz\"").is_err());
+        assert!(serde_json::from_str::<$t>(&format!("\"{}00\"", hex)).is_err());
     };
 }
 
 macro_rules! str_test {
     ($t:ty, $x:expr) => {
-        for base in [10, 16] {
-            let s = $x.get_str(base);
-            let mut y: $t = unsafe { <$t>::uninit() };
-            assert!(y.set_str(&s, base));
+        for base in [2, 10, 16] {
+            let s = $x.get_str(base).unwrap();
+            let mut y: $t = <$t>::zero();
+            assert!(y.set_str(&s, base).is_ok());
             assert_eq!($x, y);
         }
     };
@@ -178,7 +315,8 @@ fn testCurve(curve: CurveType) {
     assert_eq!(mem::size_of::<G1>(), 48 * 3);
     assert_eq!(mem::size_of::<G2>(), 48 * 2 * 3);
     assert_eq!(mem::size_of::<GT>(), 48 * 12);
-    assert!(init(curve));
+    assert!(init(curve).is_ok());
+    assert_eq!(get_curve_type(), Some(curve));
     let b = match curve {
         CurveType::BN254 => 32,
         _ => 48,
@@ -187,44 +325,68 @@ fn testCurve(curve: CurveType) {
     assert_eq!(get_g1_serialized_size(), b);
     assert_eq!(get_g2_serialized_size(), b * 2);
     assert_eq!(get_gt_serialized_size(), b * 12);
+    assert_eq!(
+        curve.fr_serialized_size(),
+        Some(get_fr_serialized_size() as usize)
+    );
+    assert_eq!(
+        curve.fp_serialized_size(),
+        Some(get_fp_serialized_size() as usize)
+    );
+    assert_eq!(
+        curve.g1_serialized_size(),
+        Some(get_g1_serialized_size() as usize)
+    );
+    assert_eq!(
+        curve.g2_serialized_size(),
+        Some(get_g2_serialized_size() as usize)
+    );
+    assert_eq!(
+        curve.gt_serialized_size(),
+        Some(get_gt_serialized_size() as usize)
+    );
     assert_eq!(get_fr_serialized_size(), 32);
+    assert!(curve.fr_serialized_size().unwrap() <= Fr::MAX_SERIALIZED_SIZE);
+    assert!(curve.g2_serialized_size().unwrap() <= G2::MAX_SERIALIZED_SIZE);
+    assert!(curve.gt_serialized_size().unwrap() <= GT::MAX_SERIALIZED_SIZE);
+    assert_eq!(CurveType::BN381.fr_serialized_size(), None);
 
     field_test! {Fr};
     field_test! {Fp};
 
     let mut P = G1::zero();
     let mut Q = G2::zero();
-    P.set_hash_of(b"abc");
-    Q.set_hash_of(b"abc");
+    P.set_hash_of(b"abc").unwrap();
+    Q.set_hash_of(b"abc").unwrap();
 
     match curve {
         CurveType::BN254 => {
             // Fp
             assert_eq!(
-                get_field_order(),
+                get_field_order().unwrap(),
                 "16798108731015832284940804142231733909889187121439069848933715426072753864723"
             );
             // Fr
             assert_eq!(
-                get_curve_order(),
+                get_curve_order().unwrap(),
                 "16798108731015832284940804142231733909759579603404752749028378864165570215949"
             );
         }
         CurveType::BLS12_381 => {
             // Fp
-            assert_eq!(get_field_order(), "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787");
+            assert_eq!(get_field_order().unwrap(), "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787");
             // Fr
             assert_eq!(
-                get_curve_order(),
+                get_curve_order().unwrap(),
                 "52435875175126190479447740508185965837690552500527637822603658699938581184513"
             );
         }
         CurveType::BLS12_377 => {
             // Fp
-            assert_eq!(get_field_order(), "258664426012969094010652733694893533536393512754914660539884262666720468348340822774968888139573360124440321458177");
+            assert_eq!(get_field_order().unwrap(), "258664426012969094010652733694893533536393512754914660539884262666720468348340822774968888139573360124440321458177");
             // Fr
             assert_eq!(
-                get_curve_order(),
+                get_curve_order().unwrap(),
                 "8444461749428370424248824938781546531375899335154063827935233455917409239041"
             );
         }
@@ -238,20 +400,631 @@ fn testCurve(curve: CurveType) {
 
     let x = Fr::from_int(3);
     let y = Fp::from_int(-1);
-    let mut e = unsafe { GT::uninit() };
+    let mut e = GT::zero();
     pairing(&mut e, &P, &Q);
+    let mut e2 = GT::zero();
+    GT::pow(&mut e2, &e, &x);
+    assert_eq!(e.pow_scalar(&x), e2);
+    let mut r = Fr::zero();
+    r.set_by_csprng();
+    for y in [x.clone(), r, Fr::zero(), Fr::one(), -Fr::one()] {
+        let mut e3 = GT::zero();
+        GT::pow_ladder(&mut e3, &e, &y);
+        assert_eq!(e3, e.pow_scalar(&y));
+        assert_eq!(e.pow_scalar_ladder(&y), e3);
+    }
+    assert_eq!(e.square(), e * e);
+    assert_eq!([e, e].iter().product::<GT>(), e.square());
+    let z2 = [Q.x, Q.y];
+    assert_eq!(z2.iter().product::<Fp2>(), Q.x * Q.y);
+    assert_eq!(z2.iter().sum::<Fp2>(), Q.x + Q.y);
+    assert_eq!(Vec::<Fp2>::new().into_iter().product::<Fp2>(), Fp2::one());
+    assert_eq!(format!("{:?}", x), "Fr(<redacted>)");
+    let mut set = HashSet::new();
+    set.insert(e);
+    set.insert(e.square());
+    set.insert(e * e);
+    assert_eq!(set.len(), 2);
+
     serialize_test! {Fr, x};
     serialize_test! {Fp, y};
     serialize_test! {G1, P};
     serialize_test! {G2, Q};
     serialize_test! {GT, e};
     serialize_test! {Fp2, Q.x};
+    if curve == CurveType::BN254 {
+        assert_eq!(
+            P.to_compressed::<32>().unwrap()[..],
+            P.serialize().unwrap()[..]
+        );
+        assert_eq!(
+            Q.to_compressed::<64>().unwrap()[..],
+            Q.serialize().unwrap()[..]
+        );
+        assert_eq!(P.to_compressed::<48>(), Err(Error::InvalidLength));
+    } else {
+        assert_eq!(
+            P.to_compressed::<48>().unwrap()[..],
+            P.serialize().unwrap()[..]
+        );
+        assert_eq!(
+            Q.to_compressed::<96>().unwrap()[..],
+            Q.serialize().unwrap()[..]
+        );
+        assert_eq!(P.to_compressed::<32>(), Err(Error::BufferTooSmall));
+    }
+    let mut s = [0u8; 16];
+    let n = Fr::from_int(255).get_str_into(&mut s, 16).unwrap();
+    assert_eq!(&s[..n], b"ff");
+    assert_eq!(P.get_str_into(&mut s, 10), Err(Error::BufferTooSmall));
 
     str_test! {Fr,x};
     str_test! {Fp, y};
     str_test! {G1, P};
     str_test! {G2, Q};
     str_test! {GT, e};
+
+    assert_eq!(x.get_str(IoMode::HEX | IoMode::PREFIX).unwrap(), "0x3");
+    // binary needs one character per bit
+    let m = -Fp::one();
+    let s = m.get_str(IoMode::BIN).unwrap();
+    assert!(s.len() > 250 && s.bytes().all(|c| c == b'0' || c == b'1'));
+    assert_eq!(Fp::from_str_radix(&s, IoMode::BIN), Ok(m));
+    let m = -Fr::one();
+    assert_eq!(
+        Fr::from_str_radix(&m.get_str(IoMode::BIN).unwrap(), IoMode::BIN),
+        Ok(m)
+    );
+    assert_eq!(Fr::from_str_radix("11", IoMode::BIN).unwrap(), x);
+    let hex: String = x
+        .serialize()
+        .unwrap()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    assert_eq!(x.get_str(IoMode::SERIALIZE_HEX_STR).unwrap(), hex);
+    let mut m = IoMode::EC_COMP;
+    m |= IoMode::HEX;
+    assert!(m.contains(IoMode::HEX) && !m.contains(IoMode::DEC));
+    assert_eq!(m.bits(), 256 | 16);
+
+    if curve == CurveType::BLS12_381 {
+        let eth = get_eth_serialization();
+        let points: HashSet<G1> = vec![P].into_iter().collect();
+        let elems: HashSet<Fp> = vec![y].into_iter().collect();
+        set_eth_serialization(true);
+        assert!(get_eth_serialization());
+        // hashing does not depend on the serialization mode
+        assert!(points.contains(&P) && elems.contains(&y));
+        // big endian, with the compression and infinity flags in the top bits
+        let mut one = vec![0u8; 32];
+        one[31] = 1;
+        assert_eq!(Fr::from_int(1).serialize().unwrap(), one);
+        let mut inf = vec![0u8; 48];
+        inf[0] = 0xc0;
+        assert_eq!(G1::zero().serialize().unwrap(), inf);
+        let mut P2 = P;
+        P2.deserialize(&inf).unwrap();
+        assert!(P2.is_zero());
+        assert_eq!(P.serialize().unwrap()[0] & 0x80, 0x80);
+        serialize_test! {G1, P};
+        serialize_test! {G2, Q};
+        set_eth_serialization(eth);
+    }
+
+    #[cfg(feature = "subtle")]
+    {
+        use subtle::{ConditionallySelectable, ConstantTimeEq};
+        assert!(bool::from(x.ct_eq(&Fr::from_int(3))));
+        assert!(!bool::from(x.ct_eq(&Fr::from_int(4))));
+        let (a, b) = (Fr::from_int(1), Fr::from_int(2));
+        assert_eq!(Fr::conditional_select(&a, &b, 0.into()), a);
+        assert_eq!(Fr::conditional_select(&a, &b, 1.into()), b);
+        assert_eq!(x.ct_inverse().unwrap(), x.inverse().unwrap());
+        assert!(bool::from(Fr::zero().ct_inverse().is_none()));
+        assert_eq!(y.ct_sqrt().is_some().unwrap_u8(), y.sqrt().is_ok() as u8);
+        assert_eq!(Fp::from_int(4).ct_sqrt().unwrap().square(), Fp::from_int(4));
+        assert!(bool::from(e.ct_eq(&e)));
+        assert!(!bool::from(e.ct_eq(&e.square())));
+
+        // 3P in jacobian and affine form compare equal
+        let P3 = P * Fr::from_int(3);
+        let P3n = P3.normalized();
+        assert!(bool::from(P3.ct_eq(&P3n)));
+        assert!(!bool::from(P3.ct_eq(&P)));
+        assert!(bool::from(G1::zero().ct_eq(&(P3 - P - P - P))));
+        assert!(!bool::from(G1::zero().ct_eq(&P)));
+        assert!(bool::from((Q * Fr::from_int(3)).ct_eq(&(Q + Q + Q))));
+        assert_eq!(G1::conditional_select(&P, &P3, 1.into()), P3);
+
+        let buf = P.serialize().unwrap();
+        assert_eq!(G1::ct_deserialize(&buf).unwrap(), P);
+        assert!(bool::from(G1::ct_deserialize(&[]).is_none()));
+    }
+
+    #[cfg(feature = "zeroize")]
+    {
+        use zeroize::Zeroize;
+        let mut P2 = P;
+        P2.zeroize();
+        assert!(P2.is_zero());
+        let mut e2 = e;
+        e2.zeroize();
+        assert_eq!(e2, GT::zero());
+        let mut sk = SecretFr::new(Fr::from_int(5));
+        assert_eq!(*sk.expose(), Fr::from_int(5));
+        assert_eq!(format!("{:?}", sk), "SecretFr(<redacted>)");
+        sk.set_by_csprng();
+        assert!(!sk.expose().is_zero());
+        assert_eq!(sk.mul_g1(&P), P * sk.expose());
+        assert_eq!(sk.mul_g2(&Q), Q * sk.expose());
+        assert_eq!(sk.pow_gt(&e), e.pow_scalar(sk.expose()));
+        sk.zeroize();
+        assert!(sk.expose().is_zero());
+    }
+
+    #[cfg(feature = "rand_core")]
+    {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let a = Fr::random(&mut rng);
+        let b = Fr::random(&mut rng);
+        assert_ne!(a, b);
+        assert_eq!(Fr::random(&mut ChaCha20Rng::seed_from_u64(1)), a);
+        assert!(Fp::random(&mut rng).is_valid());
+        let P2 = G1::random(&mut rng);
+        assert!(P2.is_valid() && !P2.is_zero());
+        assert!(G2::random(&mut rng).is_valid());
+
+        // libmcl's own CSPRNG can be replaced by a seeded one
+        set_rand_func(ChaCha20Rng::seed_from_u64(2));
+        let mut c = Fr::zero();
+        c.set_by_csprng();
+        set_rand_func(ChaCha20Rng::seed_from_u64(2));
+        let mut d = Fr::zero();
+        d.set_by_csprng();
+        assert_eq!(c, d);
+        reset_rand_func();
+        d.set_by_csprng();
+        assert_ne!(c, d);
+    }
+
+    #[cfg(feature = "sha2")]
+    {
+        // RFC 9380 K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let to_hex = |v: Vec<u8>| v.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        assert_eq!(
+            to_hex(expand_message_xmd(b"", dst, 0x20).unwrap()),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            to_hex(expand_message_xmd(b"abc", dst, 0x20).unwrap()),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+        assert_eq!(expand_message_xmd(b"abc", dst, 0x80).unwrap().len(), 0x80);
+        assert!(expand_message_xmd(b"abc", dst, 256 * 32).is_err());
+
+        let u = Fr::hash_to_field(b"abc", b"DST", 2).unwrap();
+        assert_eq!(u.len(), 2);
+        assert_ne!(u[0], u[1]);
+        let mut le = expand_message_xmd(b"abc", b"DST", 96).unwrap()[48..].to_vec();
+        le.reverse();
+        let mut v = Fr::zero();
+        v.set_little_endian_mod(&le).unwrap();
+        assert_eq!(u[1], v);
+
+        if curve == CurveType::BLS12_381 {
+            // RFC 9380 J.9.1
+            let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+            let u = Fp::hash_to_field(b"", dst, 2).unwrap();
+            let u0 = Fp::from_str_radix("0x0ba14bd907ad64a016293ee7c2d276b8eae71f25a4b941eece7b0d89f17f75cb3ae5438a614fb61d6835ad59f29c564f", 16).unwrap();
+            let u1 = Fp::from_str_radix("0x019b9bd7979f12657976de2884c7cce192b82c177c80e0ec604436a7f538d231552f0d96d9f7babe5fa3b19b3ff25ac9", 16).unwrap();
+            assert_eq!(u, vec![u0, u1]);
+            let u = Fp::hash_to_field(b"abc", dst, 2).unwrap();
+            let u0 = Fp::from_str_radix("0x0d921c33f2bad966478a03ca35d05719bdf92d347557ea166e5bba579eea9b83e9afa5c088573c2281410369fbd32951", 16).unwrap();
+            let u1 = Fp::from_str_radix("0x003574a00b109ada2f26a37a91f9d1e740dffd8d69ec0c35e1e9f4652c7dba61123e9dd2e76c655d956e2b3462611139", 16).unwrap();
+            assert_eq!(u, vec![u0, u1]);
+        }
+    }
+
+    // canonical bytes and limbs
+    let n = get_fr_serialized_size() as usize;
+    let mut one = vec![0u8; n];
+    one[0] = 1;
+    assert_eq!(Fr::one().to_bytes_le().unwrap(), one);
+    assert_eq!(Fr::from_bytes_le(&one), Ok(Fr::one()));
+    one.reverse();
+    assert_eq!(Fr::one().to_bytes_be().unwrap(), one);
+    assert_eq!(Fr::from_bytes_be(&one), Ok(Fr::one()));
+    assert_eq!(Fr::from_bytes_be(&[1, 0]), Ok(Fr::from_int(256)));
+    let mut x1 = Fr::zero();
+    x1.set_big_endian_mod(&[1, 0]).unwrap();
+    assert_eq!(x1, Fr::from_int(256));
+    let mut r = Fr::zero();
+    r.set_by_csprng();
+    assert_eq!(Fr::from_bytes_le(&r.to_bytes_le().unwrap()), Ok(r.clone()));
+    assert_eq!(Fr::from_bytes_be(&r.to_bytes_be().unwrap()), Ok(r.clone()));
+    assert_eq!(
+        Fr::from_u64_limbs(&r.to_u64_limbs().unwrap()),
+        Ok(r.clone())
+    );
+    let a: [u8; 32] = (&r).try_into().unwrap();
+    assert_eq!(Fr::try_from(a), Ok(r.clone()));
+    // -1 + 1 is the modulus, which is not canonical
+    let mut m = (-Fr::one()).to_bytes_le().unwrap();
+    m[0] += 1;
+    assert_eq!(Fr::from_bytes_le(&m), Err(Error::InvalidEncoding));
+    assert_eq!(Fr::from_bytes_le(&[0xff; 64]), Err(Error::InvalidEncoding));
+    assert_eq!(
+        Fr::from_u64_limbs(&[u64::MAX; 4]),
+        Err(Error::InvalidEncoding)
+    );
+    let u = u128::MAX;
+    assert_eq!(
+        Fr::from_u128(u),
+        Fr::from_str_radix(&u.to_string(), 10).unwrap()
+    );
+    assert_eq!(
+        Fp::from_u128(u).to_u64_limbs().unwrap()[..3],
+        [u64::MAX, u64::MAX, 0]
+    );
+    let mut t = Fp::zero();
+    t.set_by_csprng();
+    let a: [u8; 48] = (&t).try_into().unwrap();
+    assert_eq!(Fp::try_from(a), Ok(t));
+    assert_eq!(
+        t.to_bytes_le().unwrap().len(),
+        get_fp_serialized_size() as usize
+    );
+
+    #[cfg(feature = "num-bigint")]
+    {
+        use num_bigint::BigUint;
+        let b = BigUint::try_from(&r).unwrap();
+        assert_eq!(b.to_string(), r.get_str(10).unwrap());
+        assert_eq!(Fr::try_from(&b), Ok(r));
+        let order: BigUint = Fr::get_order().unwrap().parse().unwrap();
+        assert_eq!(Fr::try_from(&order), Err(Error::InvalidEncoding));
+        assert_eq!(
+            BigUint::try_from(t).unwrap().to_string(),
+            t.get_str(10).unwrap()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    {
+        serde_test! {Fr, x};
+        serde_test! {Fp, y};
+        serde_test! {Fp2, Q.x};
+        serde_test! {G1, P};
+        serde_test! {G2, Q};
+        serde_test! {GT, e};
+    }
+
+    let g1 = G1::generator().unwrap();
+    let g2 = G2::generator().unwrap();
+    assert!(!g1.is_zero() && g1.is_valid());
+    assert!(!g2.is_zero() && g2.is_valid());
+    let mut e1 = GT::zero();
+    pairing(&mut e1, &g1, &g2);
+    assert!(!e1.is_one());
+    if curve == CurveType::BLS12_381 {
+        let eth = get_eth_serialization();
+        set_eth_serialization(true);
+        let s: String = g1
+            .serialize()
+            .unwrap()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        assert_eq!(s, "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb");
+        set_eth_serialization(eth);
+    }
+    G1::set_generator(&P).unwrap();
+    G2::set_generator(&Q).unwrap();
+    assert_eq!(G1::generator().unwrap(), P);
+    assert_eq!(G2::generator().unwrap(), Q);
+    assert_eq!(G1::set_generator(&G1::zero()), Err(Error::InvalidEncoding));
+    let mut off = P;
+    off.y += Fp::one();
+    assert_eq!(G1::set_generator(&off), Err(Error::InvalidEncoding));
+    assert_eq!(G1::generator().unwrap(), P);
+    G1::set_generator(&g1).unwrap();
+    G2::set_generator(&g2).unwrap();
+
+    let Qp = G2Prepared::try_from(Q).unwrap();
+    let g2p = G2Prepared::new(&g2).unwrap();
+    let mut f1 = GT::zero();
+    let mut f2 = GT::zero();
+    miller_loop(&mut f1, &P, &Q);
+    precomputed_miller_loop(&mut f2, &P, &Qp).unwrap();
+    assert_eq!(f1, f2);
+    let mut f3 = GT::zero();
+    miller_loop(&mut f3, &g1, &g2);
+    precomputed_miller_loop2(&mut f2, &P, &Qp, &g1, &g2p).unwrap();
+    assert_eq!(f2, f1 * f3);
+    // e(P, Q) e(-P, Q) = 1
+    precomputed_miller_loop2(&mut f2, &P, &Qp, &-P, &Qp).unwrap();
+    final_exp(&mut f1, &f2);
+    assert!(f1.is_one());
+
+    miller_loop(&mut f1, &P, &Q);
+    multi_miller_loop(&mut f2, &[P, g1], &[Q, g2]).unwrap();
+    assert_eq!(f2, f1 * f3);
+    multi_miller_loop_mt(&mut f1, &[P, g1], &[Q, g2], 0).unwrap();
+    assert_eq!(f1, f2);
+    multi_miller_loop(&mut f1, &[], &[]).unwrap();
+    assert!(f1.is_one());
+    assert_eq!(
+        multi_miller_loop(&mut f1, &[P], &[]),
+        Err(Error::InvalidLength)
+    );
+    assert_eq!(
+        multi_miller_loop_mt(&mut f1, &[], &[Q], 0),
+        Err(Error::InvalidLength)
+    );
+    let mut e1 = GT::zero();
+    pairing(&mut e1, &P, &Q);
+    let mut e2 = GT::zero();
+    pairing(&mut e2, &g1, &g2);
+    assert_eq!(multi_pairing(&[P, g1], &[Q, g2]), Ok(e1 * e2));
+    assert_eq!(multi_pairing(&[P, g1], &[Q]), Err(Error::InvalidLength));
+    assert!(pairing_check(&[(P, Q), (-P, Q)]));
+    assert!(pairing_check(&[(P * x.clone(), Q), (P, -(Q * x.clone()))]));
+    assert!(!pairing_check(&[(P, Q)]));
+    assert!(pairing_check(&[]));
+
+    // 3-out-of-5 sharing of s, also in the exponent
+    let c: Vec<Fr> = [7, 11, 13].iter().map(|&i| Fr::from_int(i)).collect();
+    let cP: Vec<G1> = c.iter().map(|c| P * c).collect();
+    let cQ: Vec<G2> = c.iter().map(|c| Q * c).collect();
+    let ids: Vec<Fr> = (1..=5).map(Fr::from_int).collect();
+    let ys: Vec<Fr> = ids
+        .iter()
+        .map(|id| Fr::eval_polynomial(&c, id).unwrap())
+        .collect();
+    assert_eq!(ys[1], Fr::from_int(7 + 11 * 2 + 13 * 4));
+    let yP: Vec<G1> = ids
+        .iter()
+        .map(|id| G1::eval_polynomial(&cP, id).unwrap())
+        .collect();
+    let yQ: Vec<G2> = ids
+        .iter()
+        .map(|id| G2::eval_polynomial(&cQ, id).unwrap())
+        .collect();
+    assert_eq!(yP[2], P * ys[2].clone());
+    assert_eq!(yQ[2], Q * ys[2].clone());
+    let pick = |v: &[Fr]| vec![v[0].clone(), v[2].clone(), v[4].clone()];
+    assert_eq!(
+        Fr::lagrange_interpolation(&pick(&ids), &pick(&ys)),
+        Ok(c[0].clone())
+    );
+    assert_eq!(G1::lagrange_interpolation(&ids[1..4], &yP[1..4]), Ok(cP[0]));
+    assert_eq!(G2::lagrange_interpolation(&ids, &yQ), Ok(cQ[0]));
+    assert_ne!(
+        Fr::lagrange_interpolation(&ids[..2], &ys[..2]),
+        Ok(c[0].clone())
+    );
+    assert_eq!(Fr::eval_polynomial(&[], &ids[0]), Ok(Fr::zero()));
+    let dup = [ids[0].clone(), ids[1].clone(), ids[0].clone()];
+    assert_eq!(
+        Fr::lagrange_interpolation(&dup, &ys[..3]),
+        Err(Error::DuplicateId)
+    );
+    let zero = [ids[0].clone(), Fr::zero()];
+    assert_eq!(
+        G1::lagrange_interpolation(&zero, &yP[..2]),
+        Err(Error::ZeroId)
+    );
+    assert_eq!(
+        G2::lagrange_interpolation(&ids, &yQ[..2]),
+        Err(Error::InvalidLength)
+    );
+    assert_eq!(
+        Fr::lagrange_interpolation(&[], &[]),
+        Err(Error::InvalidLength)
+    );
+
+    assert!(P.is_in_subgroup() && Q.is_in_subgroup());
+    assert!(e.is_in_subgroup());
+    assert!(!GT::from_int(2).is_in_subgroup());
+    assert!(!GT::zero().is_in_subgroup());
+    assert!(get_verify_order_g1() && get_verify_order_g2());
+    // a point on the curve but outside the subgroup, y^2 = x^3 + b
+    let b = match curve {
+        CurveType::BN254 => 2,
+        CurveType::BLS12_381 => 4,
+        _ => 1,
+    };
+    let mut R = G1 {
+        x: Fp::from_int(1),
+        y: Fp::zero(),
+        z: Fp::one(),
+    };
+    while Fp::square_root(&mut R.y, &(R.x * R.x * R.x + Fp::from_int(b))).is_err() {
+        R.x += Fp::one();
+    }
+    assert!(R.is_valid());
+    let buf = R.serialize().unwrap();
+    let mut R2 = G1::zero();
+    if curve == CurveType::BN254 {
+        // the cofactor of G1 is one
+        assert!(R.is_in_subgroup());
+        R2.deserialize_checked(&buf).unwrap();
+        assert_eq!(R2, R);
+    } else {
+        assert!(!R.is_in_subgroup());
+        assert_eq!(G1::set_generator(&R), Err(Error::NotInSubgroup));
+        assert_eq!(R2.deserialize(&buf), Err(Error::NotInSubgroup));
+        assert_eq!(R2.deserialize_checked(&buf), Err(Error::NotInSubgroup));
+        assert!(R2.is_zero());
+        // strings go through the same policy
+        let s = R.get_str(IoMode::SERIALIZE_HEX_STR).unwrap();
+        assert_eq!(
+            R2.set_str(&s, IoMode::SERIALIZE_HEX_STR),
+            Err(Error::NotInSubgroup)
+        );
+        assert!(R2.is_zero());
+        assert_eq!(
+            G1::from_str_radix(&R.get_str(16).unwrap(), 16),
+            Err(Error::NotInSubgroup)
+        );
+        R2.deserialize_unchecked(&buf).unwrap();
+        assert_eq!(R2, R);
+        set_verify_order_g1(false);
+        R2.deserialize(&buf).unwrap();
+        assert_eq!(R2, R);
+        assert_eq!(G1::from_str_radix(&s, IoMode::SERIALIZE_HEX_STR), Ok(R));
+        assert_eq!(R2.deserialize_checked(&buf), Err(Error::NotInSubgroup));
+        set_verify_order_g1(true);
+    }
+    let buf = Q.serialize().unwrap();
+    let mut Q2 = G2::zero();
+    Q2.deserialize_checked(&buf).unwrap();
+    assert_eq!(Q2, Q);
+    // a Miller loop output is in Fp12 but not in GT
+    assert!(get_verify_order_gt());
+    let mut f = GT::zero();
+    miller_loop(&mut f, &P, &Q);
+    assert!(!f.is_in_subgroup());
+    let buf = f.serialize().unwrap();
+    let mut f2 = GT::zero();
+    assert_eq!(f2.deserialize(&buf), Err(Error::NotInSubgroup));
+    assert_eq!(f2.deserialize_checked(&buf), Err(Error::NotInSubgroup));
+    assert!(f2.is_zero());
+    assert_eq!(
+        GT::from_str_radix(&f.get_str(16).unwrap(), 16),
+        Err(Error::NotInSubgroup)
+    );
+    #[cfg(feature = "serde")]
+    assert!(serde_json::from_str::<GT>(&serde_json::to_string(&f).unwrap()).is_err());
+    f2.deserialize_unchecked(&buf).unwrap();
+    assert_eq!(f2, f);
+    set_verify_order_gt(false);
+    f2.deserialize(&buf).unwrap();
+    assert_eq!(f2, f);
+    assert_eq!(GT::from_str_radix(&f.get_str(16).unwrap(), 16), Ok(f));
+    set_verify_order_gt(true);
+
+    // affine coordinates and x || y
+    let b = b as usize;
+    let (x1, y1) = P.to_affine().unwrap();
+    assert_eq!(G1::from_affine(&x1, &y1), Ok(P));
+    assert_eq!(
+        G1::from_affine(&x1, &(y1 + Fp::one())),
+        Err(Error::InvalidEncoding)
+    );
+    let (x2, y2) = Q.to_affine().unwrap();
+    assert_eq!(G2::from_affine(&x2, &y2), Ok(Q));
+    assert_eq!(G1::zero().to_affine(), None);
+    let u = P.to_uncompressed().unwrap();
+    assert_eq!(u.len(), b * 2);
+    assert_eq!(u[..b], x1.serialize().unwrap()[..]);
+    assert_eq!(G1::from_uncompressed(&u), Ok(P));
+    assert_eq!(G1::from_uncompressed(&u[1..]), Err(Error::InvalidEncoding));
+    let u = Q.to_uncompressed().unwrap();
+    assert_eq!(u.len(), b * 4);
+    assert_eq!(G2::from_uncompressed(&u), Ok(Q));
+    let u = G2::zero().to_uncompressed().unwrap();
+    assert!(u.iter().all(|&c| c == 0));
+    assert!(G2::from_uncompressed(&u).unwrap().is_zero());
+    let mut out = [0u8; 96];
+    assert_eq!(
+        P.to_uncompressed_into(&mut out[..b]),
+        Err(Error::BufferTooSmall)
+    );
+    assert_eq!(P.to_uncompressed_into(&mut out), Ok(b * 2));
+    let u = R.to_uncompressed().unwrap();
+    assert_eq!(G1::from_uncompressed_unchecked(&u), Ok(R));
+    if curve != CurveType::BN254 {
+        assert_eq!(G1::from_uncompressed(&u), Err(Error::NotInSubgroup));
+    }
+    if curve == CurveType::BLS12_381 {
+        // in ETH mode x || y is big endian, 48 bytes per coordinate
+        let x = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+        let y = "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+        let hex = |v: Vec<u8>| -> String { v.iter().map(|b| format!("{:02x}", b)).collect() };
+        let eip = hex(g1.to_eip2537().unwrap());
+        let eth = get_eth_serialization();
+        set_eth_serialization(true);
+        assert_eq!(hex(g1.to_uncompressed().unwrap()), format!("{}{}", x, y));
+        // EIP-2537 pads each coordinate to 64 bytes in any mode
+        let pad = "0".repeat(32);
+        assert_eq!(eip, format!("{}{}{}{}", pad, x, pad, y));
+        assert_eq!(hex(g1.to_eip2537().unwrap()), eip);
+        set_eth_serialization(eth);
+
+        let u = g1.to_eip2537().unwrap();
+        assert_eq!(u.len(), 128);
+        assert_eq!(G1::from_eip2537(&u), Ok(g1));
+        let mut bad = u.clone();
+        bad[0] = 1;
+        assert_eq!(G1::from_eip2537(&bad), Err(Error::InvalidEncoding));
+        assert_eq!(G1::from_eip2537(&u[..64]), Err(Error::InvalidEncoding));
+        let mut buf = [0xffu8; 130];
+        assert_eq!(g1.to_eip2537_into(&mut buf), Ok(128));
+        assert_eq!(&buf[..128], &u[..]);
+        assert_eq!(&buf[128..], &[0xff, 0xff]);
+        assert_eq!(
+            g1.to_eip2537_into(&mut buf[..127]),
+            Err(Error::BufferTooSmall)
+        );
+        let u = Q.to_eip2537().unwrap();
+        assert_eq!(u.len(), 256);
+        assert_eq!(G2::from_eip2537(&u), Ok(Q));
+        let u = G2::zero().to_eip2537().unwrap();
+        assert!(u.iter().all(|&c| c == 0));
+        assert!(G2::from_eip2537(&u).unwrap().is_zero());
+        let u = R.to_eip2537().unwrap();
+        assert_eq!(G1::from_eip2537_unchecked(&u), Ok(R));
+        assert_eq!(G1::from_eip2537(&u), Err(Error::NotInSubgroup));
+    } else {
+        assert_eq!(P.to_eip2537(), Err(Error::Unsupported));
+        assert_eq!(P.to_eip2537_into(&mut [0; 128]), Err(Error::Unsupported));
+        assert_eq!(G2::from_eip2537(&[0; 256]), Err(Error::Unsupported));
+    }
+
+    // switches the map-to mode, so it runs last
+    set_map_to_mode(MapToMode::Original).unwrap();
+    let mut t = Fp::zero();
+    t.set_hash_of(b"map").unwrap();
+    let mut P2 = G1::zero();
+    P2.set_hash_of(b"map").unwrap();
+    assert_eq!(t.map_to_g1().unwrap(), P2);
+    let t2 = Fp2 { d: [t, Fp::zero()] };
+    let mut Q2 = G2::zero();
+    Q2.set_hash_of(b"map").unwrap();
+    assert_eq!(t2.map_to_g2().unwrap(), Q2);
+    assert!(t.map_to_g1().unwrap().is_valid());
+
+    assert_eq!(G1::hash_to_curve(b"abc", b"DST"), Err(Error::Unsupported));
+    if curve == CurveType::BLS12_381 {
+        set_map_to_mode(MapToMode::HashToCurve).unwrap();
+        // RFC 9380 J.9.1
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let P1 = G1::from_str_radix("1 0x052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1 0x08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265", 16).unwrap();
+        assert_eq!(G1::hash_to_curve(b"", dst).unwrap(), P1);
+        let P1 = G1::from_str_radix("1 0x03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903 0x0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d", 16).unwrap();
+        assert_eq!(G1::hash_to_curve(b"abc", dst).unwrap(), P1);
+        G1::set_dst(dst).unwrap();
+        let mut P2 = G1::zero();
+        P2.set_hash_of(b"abc").unwrap();
+        assert_eq!(P2, P1);
+
+        // RFC 9380 J.10.1
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let Q1 = G2::from_str_radix("1 0x0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a 0x05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d 0x0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92 0x12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6", 16).unwrap();
+        assert_eq!(G2::hash_to_curve(b"", dst).unwrap(), Q1);
+        let Q1 = G2::from_str_radix("1 0x02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6 0x139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8 0x1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48 0x00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16", 16).unwrap();
+        assert_eq!(G2::hash_to_curve(b"abc", dst).unwrap(), Q1);
+    } else {
+        assert_eq!(
+            set_map_to_mode(MapToMode::HashToCurve),
+            Err(Error::Unsupported)
+        );
+    }
 }
 
 #[test]
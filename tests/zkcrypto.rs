@@ -0,0 +1,143 @@
+#![cfg(feature = "zkcrypto")]
+use ff::{Field, PrimeField};
+use group::prime::{PrimeCurve, PrimeCurveAffine};
+use group::{Curve, Group, GroupEncoding, UncompressedEncoding};
+use mcl_rust::zkcrypto::{bls12_377, bls12_381, bn254, snark};
+use pairing::{MillerLoopResult, MultiMillerLoop};
+
+// deterministic stand-in for a real RNG
+struct CounterRng(u64);
+
+impl rand_core::RngCore for CounterRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1);
+        self.0
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+fn field_test<F: PrimeField>(rng: &mut CounterRng) {
+    let two = F::from(2);
+    assert_eq!(F::ONE + F::ONE, two);
+    assert_eq!(F::TWO_INV * two, F::ONE);
+    assert_eq!(F::ROOT_OF_UNITY * F::ROOT_OF_UNITY_INV, F::ONE);
+    let mut w = F::ROOT_OF_UNITY;
+    for _ in 0..F::S {
+        assert_ne!(w, F::ONE);
+        w = w.square();
+    }
+    assert_eq!(w, F::ONE);
+    assert_eq!(
+        F::MULTIPLICATIVE_GENERATOR.pow_vartime([1u64 << F::S]),
+        F::DELTA
+    );
+    assert!(bool::from(F::MULTIPLICATIVE_GENERATOR.sqrt().is_none()));
+
+    let x = F::random(&mut *rng);
+    assert_eq!(F::from_repr(x.to_repr()).unwrap(), x);
+    assert_eq!(x.invert().unwrap() * x, F::ONE);
+    assert!(bool::from(F::ZERO.invert().is_none()));
+    let x2 = x.square();
+    let r = x2.sqrt().unwrap();
+    assert!(r == x || r == -x);
+    let (ok, r) = F::sqrt_ratio(&x2, &F::ONE);
+    assert!(bool::from(ok));
+    assert_eq!(r.square(), x2);
+    assert_eq!([x, x].iter().sum::<F>(), x.double());
+    assert_eq!([x, x].iter().product::<F>(), x2);
+    assert_eq!(F::from(3).to_repr().as_ref()[0], 3);
+    assert!(bool::from(F::from(3).is_odd()));
+    assert_eq!(F::from_str_vartime("12345"), Some(F::from(12345)));
+
+    // the modulus itself is not canonical
+    let mut m = (-F::ONE).to_repr();
+    m.as_mut()[0] += 1;
+    assert!(bool::from(F::from_repr(m).is_none()));
+}
+
+fn curve_test<G: PrimeCurve>(rng: &mut CounterRng)
+where
+    G::Affine: UncompressedEncoding,
+{
+    let g = G::generator();
+    let a = G::Scalar::random(&mut *rng);
+    let b = G::Scalar::random(&mut *rng);
+    assert_eq!(g * a + g * b, g * (a + b));
+    assert_eq!(g.double(), g + g);
+    assert_eq!(g.double() - g - g, G::identity());
+    assert!(bool::from((g * G::Scalar::ZERO).is_identity()));
+    assert!(!bool::from(g.is_identity()));
+
+    let p = G::random(&mut *rng);
+    let pa = p.to_affine();
+    assert_eq!(pa.to_curve(), p);
+    assert_eq!(G::identity() + pa, p);
+    assert_eq!(pa * a, p * a);
+    assert_eq!([p, g].iter().sum::<G>(), p + g);
+    assert_eq!(G::from_bytes(&p.to_bytes()).unwrap(), p);
+    assert_eq!(G::Affine::from_bytes(&pa.to_bytes()).unwrap(), pa);
+    let u = pa.to_uncompressed();
+    assert_eq!(G::Affine::from_uncompressed(&u).unwrap(), pa);
+    let id = G::Affine::identity();
+    assert_eq!(
+        G::Affine::from_uncompressed(&id.to_uncompressed()).unwrap(),
+        id
+    );
+    let mut bad = u;
+    bad.as_mut()[0] ^= 1;
+    assert!(bool::from(G::Affine::from_uncompressed(&bad).is_none()));
+}
+
+fn engine_test<E: MultiMillerLoop>(rng: &mut CounterRng) {
+    field_test::<E::Fr>(rng);
+    curve_test::<E::G1>(rng);
+    curve_test::<E::G2>(rng);
+
+    let p = E::G1Affine::generator();
+    let q = E::G2Affine::generator();
+    let a = E::Fr::random(&mut *rng);
+    let e = E::pairing(&p, &q);
+    assert_eq!(e, E::Gt::generator());
+    assert!(!bool::from(e.is_identity()));
+    assert_eq!(E::pairing(&(p * a).to_affine(), &q), e * a);
+    assert_eq!(E::pairing(&p, &(q * a).to_affine()), e * a);
+    assert_eq!(e + e, e.double());
+    assert_eq!(e.double() - e, e);
+
+    // e(aP, Q) e(-P, aQ) = 1
+    let ap = (p * a).to_affine();
+    let naq = (-(q * a)).to_affine();
+    let np = -p;
+    let (q, naq): (E::G2Prepared, E::G2Prepared) = (q.into(), naq.into());
+    let r = E::multi_miller_loop(&[(&ap, &q), (&np, &naq)]);
+    assert!(bool::from(r.final_exponentiation().is_identity()));
+    assert_eq!(E::multi_miller_loop(&[(&p, &q)]).final_exponentiation(), e);
+}
+
+#[test]
+fn test_zkcrypto() {
+    let mut rng = CounterRng(1);
+    bls12_381::init().unwrap();
+    engine_test::<bls12_381::Bls12>(&mut rng);
+    snark::init().unwrap();
+    engine_test::<snark::Snark>(&mut rng);
+    bls12_377::init().unwrap();
+    engine_test::<bls12_377::Bls12_377>(&mut rng);
+    bn254::init().unwrap();
+    engine_test::<bn254::Bn254>(&mut rng);
+
+    // the wrappers of another curve refuse to run
+    let r = std::panic::catch_unwind(|| bls12_381::G1Projective::generator().double());
+    assert!(r.is_err());
+    let r = std::panic::catch_unwind(|| bls12_381::Scalar::from(3));
+    assert!(r.is_err());
+}